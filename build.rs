@@ -46,18 +46,46 @@ fn main() -> Result<()> {
 
     fs::create_dir_all(&genc_dir)?;
     let mut file = File::create(genc_dir.join(&genc_file))?;
-    file.write_all(
-        genc_code(&[
-            &genc_unique_ptr("rxx_unique_string", "std::unique_ptr<std::string>"),
-            &genc_shared_ptr("rxx_shared_string", "std::shared_ptr<std::string>"),
-            &genc_weak_ptr(
-                "rxx_weak_string",
-                "std::weak_ptr<std::string>",
-                "std::shared_ptr<std::string>",
-            ),
-        ])
-        .as_bytes(),
-    )?;
+    let mut gen_types = vec![
+        genc_unique_ptr("rxx_unique_string", "std::unique_ptr<std::string>"),
+        genc_shared_ptr("rxx_shared_string", "std::shared_ptr<std::string>"),
+        genc_weak_ptr(
+            "rxx_weak_string",
+            "std::weak_ptr<std::string>",
+            "std::shared_ptr<std::string>",
+        ),
+        genc_unique_ptr(
+            "rxx_unique_stringstream",
+            "std::unique_ptr<std::stringstream>",
+        ),
+        genc_time_point("std::chrono::system_clock::time_point", 1, 1_000_000_000),
+        genc_time_point("std::chrono::steady_clock::time_point", 1, 1_000_000_000),
+        genc_complex("float"),
+        genc_complex("double"),
+        genc_vector(
+            "rxx_vector_complex_f32",
+            "std::vector<std::complex<float>>",
+            "std::complex<float>",
+        ),
+        genc_vector(
+            "rxx_vector_complex_f64",
+            "std::vector<std::complex<double>>",
+            "std::complex<double>",
+        ),
+    ];
+    if cfg!(feature = "cxx17") {
+        gen_types.push(genc_unique_ptr(
+            "rxx_unique_path",
+            "std::unique_ptr<std::filesystem::path>",
+        ));
+        gen_types.push(genc_vector(
+            "rxx_vector_path",
+            "std::vector<std::filesystem::path>",
+            "std::filesystem::path",
+        ));
+    }
+    let gen_types: Vec<_> = gen_types.iter().map(String::as_str).collect();
+    file.write_all(genc_code(&gen_types).as_bytes())?;
 
     src_files.push(genc_file.to_str().unwrap());
 
@@ -132,14 +160,640 @@ fn main() -> Result<()> {
                         ..FnSig::default()
                     },
                 ),
+                &genc_fn(
+                    "rxx_dummy_new_any_string",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_any_string",
+                        ret_type: ReturnType::Object("std::any"),
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_unordered_map(
+                    "rxx_unordered_map_string_i64",
+                    "std::unordered_map<std::string, int64_t>",
+                    "std::string",
+                    "int64_t",
+                ),
+                &genc_unordered_map(
+                    "rxx_unordered_map_i64_vector_i64",
+                    "std::unordered_map<int64_t, std::vector<int64_t>>",
+                    "int64_t",
+                    "std::vector<int64_t>",
+                ),
+                &genc_unordered_map(
+                    "rxx_unordered_map_i64_i64",
+                    "std::unordered_map<int64_t, int64_t>",
+                    "int64_t",
+                    "int64_t",
+                ),
+                &genc_map(
+                    "rxx_map_i64_f64",
+                    "std::map<int64_t, double>",
+                    "int64_t",
+                    "double",
+                ),
+                &genc_map(
+                    "rxx_map_string_vector_i64",
+                    "std::map<std::string, std::vector<int64_t>>",
+                    "std::string",
+                    "std::vector<int64_t>",
+                ),
+                &genc_multimap(
+                    "rxx_multimap_i64_i64",
+                    "std::multimap<int64_t, int64_t>",
+                    "int64_t",
+                    "int64_t",
+                ),
+                &genc_set("rxx_set_string", "std::set<std::string>", "std::string"),
+                &genc_set("rxx_set_i64", "std::set<int64_t>", "int64_t"),
+                &genc_unordered_set(
+                    "rxx_unordered_set_i64",
+                    "std::unordered_set<int64_t>",
+                    "int64_t",
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_tags",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_tags",
+                        ret_type: ReturnType::Object("std::unique_ptr<std::set<std::string>>"),
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_unordered_set_sum",
+                    FnSig {
+                        c_fn: "dummy_cpp_unordered_set_sum",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[("std::unordered_set<int64_t> const &", "s")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_deque("rxx_deque_i64", "std::deque<int64_t>", "int64_t"),
+                &genc_deque(
+                    "rxx_deque_vector_i64",
+                    "std::deque<std::vector<int64_t>>",
+                    "std::vector<int64_t>",
+                ),
+                &genc_list("rxx_list_i64", "std::list<int64_t>", "int64_t"),
+                &genc_fn(
+                    "rxx_dummy_cpp_deque_sum",
+                    FnSig {
+                        c_fn: "dummy_cpp_deque_sum",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[("std::deque<int64_t> const &", "d")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_list",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_list",
+                        ret_type: ReturnType::Object("std::unique_ptr<std::list<int64_t>>"),
+                        args: &[("int64_t", "n")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_function(
+                    "rxx_function_i64_i64",
+                    FnSig {
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[("int64_t", "x")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_function(
+                    "rxx_function_i64_vector_i64",
+                    FnSig {
+                        ret_type: ReturnType::Object("std::vector<int64_t>"),
+                        args: &[("int64_t", "n")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_function(
+                    "rxx_function_i64_void",
+                    FnSig {
+                        args: &[("int64_t", "x")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_make_adder",
+                    FnSig {
+                        c_fn: "dummy_cpp_make_adder",
+                        ret_type: ReturnType::Object("std::function<int64_t(int64_t)>"),
+                        args: &[("int64_t", "n")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_apply_twice",
+                    FnSig {
+                        c_fn: "dummy_cpp_apply_twice",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[
+                            ("std::function<int64_t(int64_t)> const &", "f"),
+                            ("int64_t", "x"),
+                        ],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_make_iota",
+                    FnSig {
+                        c_fn: "dummy_cpp_make_iota",
+                        ret_type: ReturnType::Object(
+                            "std::function<std::vector<int64_t>(int64_t)>",
+                        ),
+                        args: &[("int64_t", "start")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_make_sink",
+                    FnSig {
+                        c_fn: "dummy_cpp_make_sink",
+                        ret_type: ReturnType::Object("std::function<void(int64_t)>"),
+                        args: &[("int64_t *", "total")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_call_copies",
+                    FnSig {
+                        c_fn: "dummy_cpp_call_copies",
+                        args: &[
+                            ("std::function<void(int64_t)> const &", "f"),
+                            ("int64_t", "n"),
+                        ],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_for_each_node",
+                    FnSig {
+                        c_fn: "dummy_cpp_for_each_node",
+                        args: &[
+                            ("std::vector<int64_t> const &", "nodes"),
+                            ("std::function<void(int64_t)> const &", "visit"),
+                        ],
+                        callbacks: &[("visit", "void(int64_t)")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_for_each_word",
+                    FnSig {
+                        c_fn: "dummy_cpp_for_each_word",
+                        args: &[
+                            ("int64_t", "n"),
+                            ("std::function<void(std::string const &)> const &", "visit"),
+                        ],
+                        callbacks: &[("visit", "void(std::string const &)")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_fold",
+                    FnSig {
+                        c_fn: "dummy_cpp_fold",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[
+                            ("int64_t", "n"),
+                            ("int64_t", "init"),
+                            ("rxx::callback<int64_t(int64_t, int64_t)>", "f"),
+                        ],
+                        callbacks: &[("f", "int64_t(int64_t, int64_t)")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_collect",
+                    FnSig {
+                        c_fn: "dummy_cpp_collect",
+                        ret_type: ReturnType::Object("std::vector<int64_t>"),
+                        args: &[
+                            ("int64_t", "n"),
+                            (
+                                "std::function<std::vector<int64_t>(int64_t)> const &",
+                                "gen",
+                            ),
+                        ],
+                        callbacks: &[("gen", "std::vector<int64_t>(int64_t)")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_register_handler",
+                    FnSig {
+                        c_fn: "dummy_cpp_register_handler",
+                        args: &[
+                            ("rxx::c_callback<void(int64_t)>::fn_t", "cb"),
+                            ("void *", "ctx"),
+                        ],
+                        c_callbacks: &[("cb", "ctx", "void(int64_t)")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_fire_event",
+                    FnSig {
+                        c_fn: "dummy_cpp_fire_event",
+                        args: &[("int64_t", "ev")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_unregister_handlers",
+                    FnSig {
+                        c_fn: "dummy_cpp_unregister_handlers",
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_call_c_callback",
+                    FnSig {
+                        c_fn: "dummy_cpp_call_c_callback",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[
+                            ("int64_t", "x"),
+                            ("void *", "ctx"),
+                            ("rxx::c_callback<int64_t(int64_t)>::fn_t", "cb"),
+                        ],
+                        c_callbacks: &[("cb", "ctx", "int64_t(int64_t)")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_complex_mul",
+                    FnSig {
+                        c_fn: "dummy_cpp_complex_mul",
+                        ret_type: ReturnType::Atomic("std::complex<double>"),
+                        args: &[("std::complex<double>", "a"), ("std::complex<double>", "b")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_complex_conj_f32",
+                    FnSig {
+                        c_fn: "dummy_cpp_complex_conj_f32",
+                        ret_type: ReturnType::Atomic("std::complex<float>"),
+                        args: &[("std::complex<float>", "c")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_spectrum",
+                    FnSig {
+                        c_fn: "dummy_cpp_spectrum",
+                        ret_type: ReturnType::Object("std::vector<std::complex<double>>"),
+                        args: &[("int64_t", "n")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_spectrum_energy",
+                    FnSig {
+                        c_fn: "dummy_cpp_spectrum_energy",
+                        ret_type: ReturnType::Atomic("double"),
+                        args: &[("std::vector<std::complex<double>> const &", "s")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_checked_div",
+                    FnSig {
+                        c_fn: "dummy_cpp_checked_div",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[
+                            ("int64_t", "a"),
+                            ("int64_t", "b"),
+                            ("std::error_code &", "ec"),
+                        ],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_remove_missing",
+                    FnSig {
+                        c_fn: "dummy_cpp_remove_missing",
+                        args: &[("int64_t", "id"), ("std::error_code &", "ec")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_checked_range",
+                    FnSig {
+                        c_fn: "dummy_cpp_checked_range",
+                        ret_type: ReturnType::Object("std::vector<int64_t>"),
+                        args: &[("int64_t", "n"), ("std::error_code &", "ec")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_parse_port",
+                    FnSig {
+                        c_fn: "dummy_cpp_parse_port",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[("int64_t", "port")],
+                        catch_system_error: true,
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_sum_words",
+                    FnSig {
+                        c_fn: "dummy_cpp_sum_words",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[("std::istream &", "input")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_log_to",
+                    FnSig {
+                        c_fn: "dummy_cpp_log_to",
+                        args: &[("std::ostream &", "out"), ("int64_t", "n")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_null_ostream",
+                    FnSig {
+                        c_fn: "dummy_cpp_null_ostream",
+                        ret_type: ReturnType::Atomic("std::ostream &"),
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_duration("std::chrono::milliseconds", 1, 1000),
+                &genc_fn(
+                    "rxx_dummy_cpp_double_timeout",
+                    FnSig {
+                        c_fn: "dummy_cpp_double_timeout",
+                        ret_type: ReturnType::Atomic("std::chrono::milliseconds"),
+                        args: &[("std::chrono::milliseconds", "timeout")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_add_hour",
+                    FnSig {
+                        c_fn: "dummy_cpp_add_hour",
+                        ret_type: ReturnType::Atomic("std::chrono::system_clock::time_point"),
+                        args: &[("std::chrono::system_clock::time_point", "t")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_steady_millis",
+                    FnSig {
+                        c_fn: "dummy_cpp_steady_millis",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[
+                            ("std::chrono::steady_clock::time_point", "start"),
+                            ("std::chrono::steady_clock::time_point", "end"),
+                        ],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_series",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_series",
+                        ret_type: ReturnType::Object("std::unique_ptr<std::map<int64_t, double>>"),
+                        args: &[("int64_t", "n")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_string_map",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_string_map",
+                        ret_type: ReturnType::Object(
+                            "std::unique_ptr<std::unordered_map<std::string, int64_t>>",
+                        ),
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_string_map_sum",
+                    FnSig {
+                        c_fn: "dummy_cpp_string_map_sum",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[("std::unordered_map<std::string, int64_t> const &", "m")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_array("double", 3),
+                &genc_vector(
+                    "rxx_vector_array_f64_3",
+                    "std::vector<std::array<double, 3>>",
+                    "std::array<double, 3>",
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_cross",
+                    FnSig {
+                        c_fn: "dummy_cpp_cross",
+                        ret_type: ReturnType::Object("std::array<double, 3>"),
+                        args: &[
+                            ("std::array<double, 3>", "a"),
+                            ("std::array<double, 3>", "b"),
+                        ],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_points",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_points",
+                        ret_type: ReturnType::Object("std::vector<std::array<double, 3>>"),
+                        args: &[("int", "n")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_point_norm2",
+                    FnSig {
+                        c_fn: "dummy_cpp_point_norm2",
+                        ret_type: ReturnType::Atomic("double"),
+                        args: &[("DummyPoint const &", "p")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_pair("int32_t", "double"),
+                &genc_pair("int64_t", "std::vector<int64_t>"),
+                &genc_tuple(
+                    "rxx_tuple_i32_f64_vector_i64",
+                    &["int32_t", "double", "std::vector<int64_t>"],
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_pair",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_pair",
+                        ret_type: ReturnType::Object("std::pair<int32_t, double>"),
+                        args: &[("int32_t", "a")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_pair_vector",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_pair_vector",
+                        ret_type: ReturnType::Object("std::pair<int64_t, std::vector<int64_t>>"),
+                        args: &[("int64_t", "a")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_tuple",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_tuple",
+                        ret_type: ReturnType::Object(
+                            "std::tuple<int32_t, double, std::vector<int64_t>>",
+                        ),
+                        args: &[("int32_t", "a")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_tuple_sum",
+                    FnSig {
+                        c_fn: "dummy_cpp_tuple_sum",
+                        ret_type: ReturnType::Atomic("double"),
+                        args: &[(
+                            "std::tuple<int32_t, double, std::vector<int64_t>> const &",
+                            "t",
+                        )],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_any("rxx_any_i64", "int64_t"),
+                &genc_optional("rxx_optional_i64", "int64_t"),
+                &genc_variant(
+                    "rxx_DummyMsg",
+                    "DummyMsg",
+                    &[
+                        ("Int", "int64_t"),
+                        ("Float", "double"),
+                        ("Values", "std::vector<int64_t>"),
+                    ],
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_new_msg",
+                    FnSig {
+                        c_fn: "dummy_cpp_new_msg",
+                        ret_type: ReturnType::Object("DummyMsg"),
+                        args: &[("int64_t", "kind")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_msg_sum",
+                    FnSig {
+                        c_fn: "dummy_cpp_msg_sum",
+                        ret_type: ReturnType::Atomic("double"),
+                        args: &[("DummyMsg const &", "msg")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_optional("rxx_optional_vector_i64", "std::vector<int64_t>"),
+                &genc_fn(
+                    "rxx_dummy_cpp_find_i64",
+                    FnSig {
+                        c_fn: "dummy_cpp_find_i64",
+                        ret_type: ReturnType::Object("std::optional<int64_t>"),
+                        args: &[("std::vector<int64_t> const &", "v"), ("int64_t", "val")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_value_or_i64",
+                    FnSig {
+                        c_fn: "dummy_cpp_value_or_i64",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[("std::optional<int64_t> const &", "v"), ("int64_t", "def")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_parent_path",
+                    FnSig {
+                        c_fn: "dummy_cpp_parent_path",
+                        ret_type: ReturnType::Object("std::unique_ptr<std::filesystem::path>"),
+                        args: &[("std::filesystem::path const &", "p")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_dummy_cpp_path_parts",
+                    FnSig {
+                        c_fn: "dummy_cpp_path_parts",
+                        ret_type: ReturnType::Object("std::vector<std::filesystem::path>"),
+                        args: &[("std::filesystem::path const &", "p")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_any("rxx_any_string", "std::string"),
+                &genc_type_info("rxx_type_info_config", "DummyConfig"),
                 &genc_unique_ptr("rxx_unique_i64", "std::unique_ptr<int64_t>"),
                 &genc_shared_ptr("rxx_shared_i64", "std::shared_ptr<int64_t>"),
+                &genc_make_shared("rxx_shared_i64", "std::shared_ptr<int64_t>"),
+                &genc_make_shared("rxx_shared_string", "std::shared_ptr<std::string>"),
+                &genc_atomic_shared_ptr("rxx_shared_i64", "std::shared_ptr<int64_t>"),
                 &genc_weak_ptr(
                     "rxx_weak_i64",
                     "std::weak_ptr<int64_t>",
                     "std::shared_ptr<int64_t>",
                 ),
                 &genc_vector("rxx_vector_i64", "std::vector<int64_t>", "int64_t"),
+                &genc_shared_ptr(
+                    "rxx_shared_vector_i64",
+                    "std::shared_ptr<std::vector<int64_t>>",
+                ),
+                &genc_make_shared(
+                    "rxx_shared_vector_i64",
+                    "std::shared_ptr<std::vector<int64_t>>",
+                ),
+                &genc_shared_ptr("rxx_shared_config", "std::shared_ptr<DummyConfig>"),
+                &genc_unique_ptr("rxx_unique_DummyBase", "std::unique_ptr<DummyBase>"),
+                &genc_shared_ptr("rxx_shared_DummyBase", "std::shared_ptr<DummyBase>"),
+                &genc_unique_ptr("rxx_unique_DummyDerived", "std::unique_ptr<DummyDerived>"),
+                &genc_shared_ptr("rxx_shared_DummyDerived", "std::shared_ptr<DummyDerived>"),
+                &genc_pointer_cast("rxx_DummyDerived_DummyBase", "DummyBase", "DummyDerived"),
+                &genc_unique_ptr("rxx_unique_DummyNode", "std::unique_ptr<DummyNode>"),
+                &genc_shared_ptr("rxx_shared_DummyNode", "std::shared_ptr<DummyNode>"),
+                &genc_weak_ptr(
+                    "rxx_weak_DummyNode",
+                    "std::weak_ptr<DummyNode>",
+                    "std::shared_ptr<DummyNode>",
+                ),
+                &genc_shared_from_this("rxx_DummyNode", "DummyNode"),
+                &genc_fn(
+                    "rxx_DummyNode_id",
+                    FnSig {
+                        cls: Some("DummyNode"),
+                        c_fn: "&$C::id",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_DummyBase_value",
+                    FnSig {
+                        cls: Some("DummyBase"),
+                        c_fn: "&$C::value",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_DummyDerived_derived_value",
+                    FnSig {
+                        cls: Some("DummyDerived"),
+                        c_fn: "&$C::derived_value",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        ..FnSig::default()
+                    },
+                ),
                 &genc_fn(
                     "rxx_Dummy_get",
                     FnSig {
@@ -158,6 +812,7 @@ fn main() -> Result<()> {
                         is_mut: true,
                         ret_type: ReturnType::Atomic("int64_t&"),
                         args: &[("size_t", "idx")],
+                        ..FnSig::default()
                     },
                 ),
                 &genc_fn(
@@ -170,6 +825,36 @@ fn main() -> Result<()> {
                         ..FnSig::default()
                     },
                 ),
+                &genc_fn(
+                    "rxx_Dummy_checked_get",
+                    FnSig {
+                        cls: Some("Dummy"),
+                        c_fn: "&$C::checked_get",
+                        ret_type: ReturnType::Atomic("int64_t"),
+                        args: &[("size_t", "idx"), ("std::error_code &", "ec")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_Dummy_checked_add",
+                    FnSig {
+                        cls: Some("Dummy"),
+                        c_fn: "&$C::checked_add",
+                        is_mut: true,
+                        args: &[("int64_t", "val"), ("std::error_code &", "ec")],
+                        ..FnSig::default()
+                    },
+                ),
+                &genc_fn(
+                    "rxx_Dummy_checked_prefix",
+                    FnSig {
+                        cls: Some("Dummy"),
+                        c_fn: "&$C::checked_prefix",
+                        ret_type: ReturnType::Object("std::vector<int64_t>"),
+                        args: &[("size_t", "n"), ("std::error_code &", "ec")],
+                        ..FnSig::default()
+                    },
+                ),
             ])
             .as_bytes(),
         )?;
@@ -180,7 +865,11 @@ fn main() -> Result<()> {
     cc::Build::new()
         .files(&src_files)
         .cpp(true)
-        .flag_if_supported("-std=c++14")
+        .flag_if_supported(if cfg!(feature = "cxx17") {
+            "-std=c++17"
+        } else {
+            "-std=c++14"
+        })
         .includes(&inc_dirs)
         .compile("rxx");
 
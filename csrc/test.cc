@@ -24,10 +24,308 @@ void dummy_cpp_getvoid_vector_i64(std::vector<int64_t>const& v, int a) {
   a += v[0];
 }
 
+std::any dummy_cpp_new_any_string() {
+  return std::string("any");
+}
+
+std::unique_ptr<std::unordered_map<std::string, int64_t>> dummy_cpp_new_string_map() {
+  return std::unique_ptr<std::unordered_map<std::string, int64_t>>(
+      new std::unordered_map<std::string, int64_t>{{"a", 1}, {"b", 2}});
+}
+
+std::unique_ptr<std::map<int64_t, double>> dummy_cpp_new_series(int64_t n) {
+  std::unique_ptr<std::map<int64_t, double>> m(new std::map<int64_t, double>);
+  for (int64_t i = n; i > 0; i--)
+    (*m)[i * 10] = i * 0.5;
+  return m;
+}
+
+std::unique_ptr<std::set<std::string>> dummy_cpp_new_tags() {
+  return std::unique_ptr<std::set<std::string>>(new std::set<std::string>{"urgent", "io", "cpu"});
+}
+
+int64_t dummy_cpp_unordered_set_sum(std::unordered_set<int64_t> const &s) {
+  int64_t sum = 0;
+  for (auto v : s)
+    sum += v;
+  return sum;
+}
+
+std::function<int64_t(int64_t)> dummy_cpp_make_adder(int64_t n) {
+  return [n](int64_t x) { return x + n; };
+}
+
+int64_t dummy_cpp_apply_twice(std::function<int64_t(int64_t)> const &f, int64_t x) {
+  return f(f(x));
+}
+
+std::function<std::vector<int64_t>(int64_t)> dummy_cpp_make_iota(int64_t start) {
+  // not trivially copyable, so the target is heap allocated
+  std::vector<int64_t> first{start};
+  return [first](int64_t n) {
+    std::vector<int64_t> v;
+    for (int64_t i = 0; i < n; i++)
+      v.push_back(first[0] + i);
+    return v;
+  };
+}
+
+std::function<void(int64_t)> dummy_cpp_make_sink(int64_t *total) {
+  return [total](int64_t x) { *total += x; };
+}
+
+void dummy_cpp_call_copies(std::function<void(int64_t)> const &f, int64_t n) {
+  std::vector<std::function<void(int64_t)>> copies(n, f);
+  for (int64_t i = 0; i < n; i++)
+    copies[i](i);
+}
+
+void dummy_cpp_for_each_node(std::vector<int64_t> const &nodes, std::function<void(int64_t)> const &visit) {
+  for (auto n : nodes)
+    visit(n);
+}
+
+void dummy_cpp_for_each_word(int64_t n, std::function<void(std::string const &)> const &visit) {
+  for (int64_t i = 0; i < n; i++)
+    visit("w" + std::to_string(i));
+}
+
+int64_t dummy_cpp_fold(int64_t n, int64_t init, rxx::callback<int64_t(int64_t, int64_t)> f) {
+  for (int64_t i = 0; i < n; i++)
+    init = f(init, i);
+  return init;
+}
+
+std::vector<int64_t> dummy_cpp_collect(int64_t n, std::function<std::vector<int64_t>(int64_t)> const &gen) {
+  std::vector<int64_t> out;
+  for (int64_t i = 0; i < n; i++)
+    for (auto v : gen(i))
+      out.push_back(v);
+  return out;
+}
+
+static std::mutex handlers_mutex;
+static std::vector<std::pair<void (*)(void *, int64_t), void *>> handlers;
+
+void dummy_cpp_register_handler(void (*cb)(void *ctx, int64_t ev), void *ctx) {
+  std::lock_guard<std::mutex> lock(handlers_mutex);
+  handlers.emplace_back(cb, ctx);
+}
+
+// calls the handlers from another thread
+void dummy_cpp_fire_event(int64_t ev) {
+  std::thread t([ev] {
+    std::lock_guard<std::mutex> lock(handlers_mutex);
+    for (auto &h : handlers)
+      h.first(h.second, ev);
+  });
+  t.join();
+}
+
+void dummy_cpp_unregister_handlers() {
+  std::lock_guard<std::mutex> lock(handlers_mutex);
+  handlers.clear();
+}
+
+int64_t dummy_cpp_call_c_callback(int64_t x, void *ctx, int64_t (*cb)(void *ctx, int64_t x)) {
+  return cb(ctx, x);
+}
+
+std::complex<double> dummy_cpp_complex_mul(std::complex<double> a, std::complex<double> b) {
+  return a * b;
+}
+
+std::complex<float> dummy_cpp_complex_conj_f32(std::complex<float> c) {
+  return std::conj(c);
+}
+
+std::vector<std::complex<double>> dummy_cpp_spectrum(int64_t n) {
+  std::vector<std::complex<double>> s;
+  for (int64_t k = 0; k < n; ++k)
+    s.emplace_back(double(k), double(-k));
+  return s;
+}
+
+double dummy_cpp_spectrum_energy(std::vector<std::complex<double>> const &s) {
+  double sum = 0;
+  for (auto &c : s)
+    sum += std::norm(c);
+  return sum;
+}
+
+int64_t dummy_cpp_checked_div(int64_t a, int64_t b, std::error_code &ec) {
+  if (b == 0) {
+    ec = std::make_error_code(std::errc::invalid_argument);
+    return 0;
+  }
+  return a / b;
+}
+
+void dummy_cpp_remove_missing(int64_t id, std::error_code &) {
+  if (id > 0)
+    throw std::system_error(ENOENT, std::system_category(), "remove");
+}
+
+namespace {
+struct DummyCategory : std::error_category {
+  const char *name() const noexcept override { return "dummy"; }
+  std::string message(int ev) const override { return "dummy error " + std::to_string(ev); }
+};
+} // namespace
+
+std::vector<int64_t> dummy_cpp_checked_range(int64_t n, std::error_code &) {
+  static DummyCategory category;
+  if (n < 0)
+    throw std::system_error(std::error_code(int(-n), category));
+  std::vector<int64_t> v;
+  for (int64_t i = 0; i < n; ++i)
+    v.push_back(i);
+  return v;
+}
+
+int64_t dummy_cpp_parse_port(int64_t port) {
+  if (port <= 0 || port > 65535)
+    throw std::system_error(std::make_error_code(std::errc::result_out_of_range), "port");
+  return port;
+}
+
+int64_t dummy_cpp_sum_words(std::istream &input) {
+  int64_t sum = 0, v;
+  while (input >> v)
+    sum += v;
+  return sum;
+}
+
+void dummy_cpp_log_to(std::ostream &out, int64_t n) {
+  for (int64_t i = 0; i < n; ++i)
+    out << "line " << i << '\n';
+}
+
+// always has badbit set, there is no streambuf
+std::ostream &dummy_cpp_null_ostream() {
+  static std::ostream out(nullptr);
+  return out;
+}
+
+std::chrono::milliseconds dummy_cpp_double_timeout(std::chrono::milliseconds timeout) {
+  return timeout * 2;
+}
+
+std::chrono::system_clock::time_point dummy_cpp_add_hour(std::chrono::system_clock::time_point t) {
+  return t + std::chrono::hours(1);
+}
+
+int64_t dummy_cpp_steady_millis(std::chrono::steady_clock::time_point start, std::chrono::steady_clock::time_point end) {
+  return std::chrono::duration_cast<std::chrono::milliseconds>(end - start).count();
+}
+
+int64_t dummy_cpp_deque_sum(std::deque<int64_t> const &d) {
+  int64_t sum = 0;
+  for (auto v : d)
+    sum += v;
+  return sum;
+}
+
+std::unique_ptr<std::list<int64_t>> dummy_cpp_new_list(int64_t n) {
+  std::unique_ptr<std::list<int64_t>> l(new std::list<int64_t>);
+  for (int64_t i = 0; i < n; i++)
+    l->push_back(i);
+  return l;
+}
+
+int64_t dummy_cpp_string_map_sum(std::unordered_map<std::string, int64_t> const &m) {
+  int64_t sum = 0;
+  for (auto &kv : m)
+    sum += kv.second;
+  return sum;
+}
+
+std::array<double, 3> dummy_cpp_cross(std::array<double, 3> a, std::array<double, 3> b) {
+  return {a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]};
+}
+
+std::vector<std::array<double, 3>> dummy_cpp_new_points(int n) {
+  std::vector<std::array<double, 3>> points;
+  for (int i = 0; i < n; i++)
+    points.push_back({double(i), double(i), double(i)});
+  return points;
+}
+
+double dummy_cpp_point_norm2(DummyPoint const &p) {
+  return p.pos[0] * p.pos[0] + p.pos[1] * p.pos[1] + p.pos[2] * p.pos[2];
+}
+
+std::pair<int32_t, double> dummy_cpp_new_pair(int32_t a) {
+  return {a, a * 0.5};
+}
+
+std::pair<int64_t, std::vector<int64_t>> dummy_cpp_new_pair_vector(int64_t a) {
+  return {a, {a, a + 1}};
+}
+
+std::tuple<int32_t, double, std::vector<int64_t>> dummy_cpp_new_tuple(int32_t a) {
+  return std::make_tuple(a, a * 0.5, std::vector<int64_t>{a, a + 1});
+}
+
+double dummy_cpp_tuple_sum(std::tuple<int32_t, double, std::vector<int64_t>> const &t) {
+  double sum = std::get<0>(t) + std::get<1>(t);
+  for (auto i : std::get<2>(t))
+    sum += i;
+  return sum;
+}
+
+std::optional<int64_t> dummy_cpp_find_i64(std::vector<int64_t> const &v, int64_t val) {
+  for (size_t i = 0; i < v.size(); i++)
+    if (v[i] == val)
+      return i;
+  return std::nullopt;
+}
+
+int64_t dummy_cpp_value_or_i64(std::optional<int64_t> const &v, int64_t def) {
+  return v.value_or(def);
+}
+
+std::unique_ptr<std::filesystem::path> dummy_cpp_parent_path(std::filesystem::path const &p) {
+  return std::make_unique<std::filesystem::path>(p.parent_path());
+}
+
+std::vector<std::filesystem::path> dummy_cpp_path_parts(std::filesystem::path const &p) {
+  return std::vector<std::filesystem::path>(p.begin(), p.end());
+}
+
+DummyMsg dummy_cpp_new_msg(int64_t kind) {
+  switch (kind) {
+  case 0:
+    return int64_t(1);
+  case 1:
+    return 1.5;
+  default:
+    return std::vector<int64_t>{1, 2, 3};
+  }
+}
+
+struct DummyMsgSum {
+  double operator()(int64_t v) const { return v; }
+  double operator()(double v) const { return v; }
+  double operator()(std::vector<int64_t> const &v) const {
+    double sum = 0;
+    for (auto i : v)
+      sum += i;
+    return sum;
+  }
+};
+
+double dummy_cpp_msg_sum(DummyMsg const &msg) {
+  return std::visit(DummyMsgSum(), msg);
+}
+
 int64_t const & dummy_cpp_getref_vector_i64(std::vector<int64_t> const &v, int idx) {
   return v[idx];
 }
 
+static std::vector<std::shared_ptr<RustHandler>> registered_handlers;
+static std::shared_ptr<int64_t> global_config;
+
 extern "C" {
 
 void rxx_dummy_new_unique_i64(int64_t v, std::unique_ptr<int64_t> *out) {
@@ -51,4 +349,55 @@ void rxx_dummy_new_shared_string(std::shared_ptr<std::string> *out) {
   new (out) std::shared_ptr<std::string>(new std::string("test"));
 }
 
+void rxx_dummy_new_shared_config(int64_t id, std::shared_ptr<DummyConfig> *out) {
+  new (out) std::shared_ptr<DummyConfig>(new DummyConfig{{1, 2, 3}, id});
+}
+
+void rxx_dummy_new_shared_base(std::shared_ptr<DummyBase> *out) {
+  new (out) std::shared_ptr<DummyBase>(new DummyBase);
+}
+
+void rxx_dummy_new_shared_derived(int64_t val, std::shared_ptr<DummyBase> *out) {
+  new (out) std::shared_ptr<DummyBase>(new DummyDerived(val));
+}
+
+void rxx_dummy_new_unique_derived(int64_t val, std::unique_ptr<DummyBase> *out) {
+  new (out) std::unique_ptr<DummyBase>(new DummyDerived(val));
+}
+
+void rxx_dummy_new_shared_node(int64_t id, std::shared_ptr<DummyNode> *out) {
+  new (out) std::shared_ptr<DummyNode>(std::make_shared<DummyNode>(id));
+}
+
+void rxx_dummy_new_unique_node(int64_t id, std::unique_ptr<DummyNode> *out) {
+  new (out) std::unique_ptr<DummyNode>(new DummyNode(id));
+}
+
+void rxx_dummy_register_handler(const std::shared_ptr<RustHandler> &handler) {
+  registered_handlers.push_back(handler);
+}
+
+size_t rxx_dummy_clear_handlers() {
+  size_t n = registered_handlers.size();
+  registered_handlers.clear();
+  return n;
+}
+
+void rxx_dummy_take_handler(std::unique_ptr<RustHandler> &handler) {
+  std::unique_ptr<RustHandler> owned(std::move(handler));
+}
+
+std::shared_ptr<int64_t> &rxx_dummy_global_config() {
+  return global_config;
+}
+
+void rxx_dummy_reload_config(int64_t val) {
+  std::atomic_store(&global_config, std::make_shared<int64_t>(val));
+}
+
+const std::type_info &rxx_dummy_plugin_i64(int64_t v, std::shared_ptr<void> *out) {
+  new (out) std::shared_ptr<void>(std::make_shared<int64_t>(v));
+  return typeid(int64_t);
+}
+
 } // extern "C"
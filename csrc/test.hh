@@ -2,7 +2,26 @@
 
 #include <cstdint>
 #include <vector>
+#include <unordered_map>
+#include <map>
+#include <set>
+#include <unordered_set>
+#include <deque>
+#include <list>
+#include <functional>
+#include <mutex>
+#include <thread>
+#include <string>
 #include <memory>
+#include <any>
+#include <tuple>
+#include <array>
+#include <utility>
+#include <optional>
+#include <variant>
+#include <wrapper.hh>
+
+RXX_RUST_TYPE(RustHandler, rxx_RustHandler)
 
 std::vector<int64_t> dummy_cpp_new_vector_i64(int a);
 
@@ -13,6 +32,67 @@ void dummy_cpp_getvoid_vector_i64(std::vector<int64_t>const& v, int a);
 
 int64_t const & dummy_cpp_getref_vector_i64(std::vector<int64_t> const &v, int idx);
 
+std::any dummy_cpp_new_any_string();
+
+std::unique_ptr<std::unordered_map<std::string, int64_t>> dummy_cpp_new_string_map();
+std::unique_ptr<std::map<int64_t, double>> dummy_cpp_new_series(int64_t n);
+std::unique_ptr<std::set<std::string>> dummy_cpp_new_tags();
+std::function<int64_t(int64_t)> dummy_cpp_make_adder(int64_t n);
+int64_t dummy_cpp_apply_twice(std::function<int64_t(int64_t)> const &f, int64_t x);
+std::function<std::vector<int64_t>(int64_t)> dummy_cpp_make_iota(int64_t start);
+std::function<void(int64_t)> dummy_cpp_make_sink(int64_t *total);
+void dummy_cpp_call_copies(std::function<void(int64_t)> const &f, int64_t n);
+void dummy_cpp_for_each_node(std::vector<int64_t> const &nodes, std::function<void(int64_t)> const &visit);
+void dummy_cpp_for_each_word(int64_t n, std::function<void(std::string const &)> const &visit);
+int64_t dummy_cpp_fold(int64_t n, int64_t init, rxx::callback<int64_t(int64_t, int64_t)> f);
+std::vector<int64_t> dummy_cpp_collect(int64_t n, std::function<std::vector<int64_t>(int64_t)> const &gen);
+void dummy_cpp_register_handler(void (*cb)(void *ctx, int64_t ev), void *ctx);
+void dummy_cpp_fire_event(int64_t ev);
+void dummy_cpp_unregister_handlers();
+int64_t dummy_cpp_call_c_callback(int64_t x, void *ctx, int64_t (*cb)(void *ctx, int64_t x));
+std::complex<double> dummy_cpp_complex_mul(std::complex<double> a, std::complex<double> b);
+std::complex<float> dummy_cpp_complex_conj_f32(std::complex<float> c);
+std::vector<std::complex<double>> dummy_cpp_spectrum(int64_t n);
+double dummy_cpp_spectrum_energy(std::vector<std::complex<double>> const &s);
+int64_t dummy_cpp_checked_div(int64_t a, int64_t b, std::error_code &ec);
+void dummy_cpp_remove_missing(int64_t id, std::error_code &ec);
+std::vector<int64_t> dummy_cpp_checked_range(int64_t n, std::error_code &ec);
+int64_t dummy_cpp_parse_port(int64_t port);
+int64_t dummy_cpp_sum_words(std::istream &input);
+void dummy_cpp_log_to(std::ostream &out, int64_t n);
+std::ostream &dummy_cpp_null_ostream();
+std::chrono::milliseconds dummy_cpp_double_timeout(std::chrono::milliseconds timeout);
+std::chrono::system_clock::time_point dummy_cpp_add_hour(std::chrono::system_clock::time_point t);
+int64_t dummy_cpp_steady_millis(std::chrono::steady_clock::time_point start, std::chrono::steady_clock::time_point end);
+int64_t dummy_cpp_deque_sum(std::deque<int64_t> const &d);
+std::unique_ptr<std::list<int64_t>> dummy_cpp_new_list(int64_t n);
+int64_t dummy_cpp_unordered_set_sum(std::unordered_set<int64_t> const &s);
+int64_t dummy_cpp_string_map_sum(std::unordered_map<std::string, int64_t> const &m);
+
+struct DummyPoint {
+  int64_t id;
+  std::array<double, 3> pos;
+};
+
+std::array<double, 3> dummy_cpp_cross(std::array<double, 3> a, std::array<double, 3> b);
+std::vector<std::array<double, 3>> dummy_cpp_new_points(int n);
+double dummy_cpp_point_norm2(DummyPoint const &p);
+
+std::pair<int32_t, double> dummy_cpp_new_pair(int32_t a);
+std::pair<int64_t, std::vector<int64_t>> dummy_cpp_new_pair_vector(int64_t a);
+std::tuple<int32_t, double, std::vector<int64_t>> dummy_cpp_new_tuple(int32_t a);
+double dummy_cpp_tuple_sum(std::tuple<int32_t, double, std::vector<int64_t>> const &t);
+
+std::optional<int64_t> dummy_cpp_find_i64(std::vector<int64_t> const &v, int64_t val);
+int64_t dummy_cpp_value_or_i64(std::optional<int64_t> const &v, int64_t def);
+std::unique_ptr<std::filesystem::path> dummy_cpp_parent_path(std::filesystem::path const &p);
+std::vector<std::filesystem::path> dummy_cpp_path_parts(std::filesystem::path const &p);
+
+using DummyMsg = std::variant<int64_t, double, std::vector<int64_t>>;
+
+DummyMsg dummy_cpp_new_msg(int64_t kind);
+double dummy_cpp_msg_sum(DummyMsg const &msg);
+
 struct Dummy {
   int64_t *data_;
   size_t len_;
@@ -28,7 +108,64 @@ struct Dummy {
       data_[i] += val;
   }
 
+  int64_t checked_get(size_t idx, std::error_code &ec) const {
+    if (idx >= len_) {
+      ec = std::make_error_code(std::errc::result_out_of_range);
+      return 0;
+    }
+    return data_[idx];
+  }
+
+  void checked_add(int64_t val, std::error_code &ec) {
+    if (val < 0) {
+      ec = std::make_error_code(std::errc::invalid_argument);
+      return;
+    }
+    add(val);
+  }
+
+  std::vector<int64_t> checked_prefix(size_t n, std::error_code &ec) const {
+    if (n > len_) {
+      ec = std::make_error_code(std::errc::result_out_of_range);
+      return {};
+    }
+    return std::vector<int64_t>(data_, data_ + n);
+  }
+
   std::unique_ptr<Dummy> create(int64_t *data, size_t len) {
     return std::make_unique<Dummy>(data, len);
   }
 };
+
+struct DummyConfig {
+  std::vector<int64_t> values;
+  int64_t id;
+};
+
+struct DummyOther {
+  virtual ~DummyOther() = default;
+  int64_t other = 1;
+};
+
+struct DummyBase {
+  virtual ~DummyBase() = default;
+  virtual int64_t value() const { return 0; }
+};
+
+struct DummyDerived : DummyOther, DummyBase {
+  int64_t val_;
+
+  explicit DummyDerived(int64_t val): val_(val) {}
+
+  int64_t value() const override { return val_; }
+
+  int64_t derived_value() const { return val_ * 2; }
+};
+
+struct DummyNode : std::enable_shared_from_this<DummyNode> {
+  int64_t id_;
+
+  explicit DummyNode(int64_t id): id_(id) {}
+
+  int64_t id() const { return id_; }
+};
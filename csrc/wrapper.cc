@@ -29,3 +29,215 @@ void rxx_string_reserve(std::string &self, size_t n) noexcept {
 void rxx_string_push(std::string &self, const uint8_t *ptr, size_t len) noexcept {
   self.append((const char*)ptr, len);
 }
+
+void rxx_shared_ptr_alias(const std::shared_ptr<void> &self, void *ptr, std::shared_ptr<void> *out) noexcept {
+  shared_ptr_alias(self, ptr, out);
+}
+
+void rxx_shared_ptr_from_raw(void *ptr, void (*deleter)(void *), std::shared_ptr<void> *out) noexcept {
+  new (out) std::shared_ptr<void>(ptr, deleter);
+}
+
+void rxx_shared_ptr_void_delete(std::shared_ptr<void> &self) noexcept {
+  destroy(&self);
+}
+
+void rxx_shared_ptr_void_clone(const std::shared_ptr<void> &self, std::shared_ptr<void> *out) noexcept {
+  shared_ptr_clone(self, out);
+}
+
+size_t rxx_shared_ptr_use_count(const std::shared_ptr<void> &self) noexcept {
+  return self.use_count();
+}
+
+const char *rxx_type_info_name(const std::type_info &self) noexcept {
+  return self.name();
+}
+
+bool rxx_type_info_eq(const std::type_info &self, const std::type_info &other) noexcept {
+  return self == other;
+}
+
+std::chrono::steady_clock::time_point rxx_steady_clock_now() noexcept {
+  return std::chrono::steady_clock::now();
+}
+
+static_assert(sizeof(std::error_code) == 2 * sizeof(void *) &&
+                  alignof(std::error_code) == alignof(void *) &&
+                  std::is_trivially_copyable<std::error_code>::value,
+              "unsupported layout of std::error_code");
+
+std::error_code rxx_error_code_new() noexcept {
+  return std::error_code();
+}
+
+const char *rxx_error_code_category_name(const std::error_code &self) noexcept {
+  return self.category().name();
+}
+
+void rxx_error_code_message(const std::error_code &self, std::unique_ptr<std::string> *out) noexcept {
+  new (out) std::unique_ptr<std::string>(new std::string(self.message()));
+}
+
+// both hold errno values on POSIX
+bool rxx_error_code_is_os_error(const std::error_code &self) noexcept {
+  return self.category() == std::generic_category() ||
+         self.category() == std::system_category();
+}
+
+namespace {
+// bits of the state as seen by Rust
+int stream_state(const std::ios &self) {
+  return (self.bad() ? 1 : 0) | (self.fail() ? 2 : 0) | (self.eof() ? 4 : 0);
+}
+
+// the get area is only reachable from a derived class
+struct streambuf_access : std::streambuf {
+  static char *get_begin(std::streambuf *sb) { return (sb->*&streambuf_access::gptr)(); }
+  static char *get_end(std::streambuf *sb) { return (sb->*&streambuf_access::egptr)(); }
+  static void get_bump(std::streambuf *sb, int n) { (sb->*&streambuf_access::gbump)(n); }
+};
+} // namespace
+
+int rxx_istream_state(const std::istream &self) noexcept {
+  return stream_state(self);
+}
+
+void rxx_istream_clear(std::istream &self) noexcept {
+  self.clear();
+}
+
+size_t rxx_istream_read(std::istream &self, char *buf, size_t len, int *state) noexcept {
+  size_t n = 0;
+  try {
+    self.read(buf, std::streamsize(len));
+    n = size_t(self.gcount());
+  } catch (...) {
+    n = size_t(self.gcount());
+  }
+  *state = stream_state(self);
+  return n;
+}
+
+const char *rxx_istream_fill_buf(std::istream &self, size_t *len, int *state) noexcept {
+  const char *data = nullptr;
+  *len = 0;
+  std::streambuf *sb = self.rdbuf();
+  if (self.good() && sb) {
+    try {
+      if (std::streambuf::traits_type::eq_int_type(sb->sgetc(), std::streambuf::traits_type::eof())) {
+        self.setstate(std::ios::eofbit);
+      } else {
+        data = streambuf_access::get_begin(sb);
+        *len = size_t(streambuf_access::get_end(sb) - data);
+      }
+    } catch (...) {
+      self.setstate(std::ios::badbit);
+    }
+  }
+  *state = stream_state(self);
+  return data;
+}
+
+// never past the get area, `gbump` takes an int
+void rxx_istream_consume(std::istream &self, size_t n) noexcept {
+  std::streambuf *sb = self.rdbuf();
+  if (!sb)
+    return;
+  size_t avail = size_t(streambuf_access::get_end(sb) - streambuf_access::get_begin(sb));
+  n = std::min(n, avail);
+  const size_t chunk = size_t(std::numeric_limits<int>::max());
+  for (; n > chunk; n -= chunk)
+    streambuf_access::get_bump(sb, int(chunk));
+  streambuf_access::get_bump(sb, int(n));
+}
+
+int rxx_ostream_state(const std::ostream &self) noexcept {
+  return stream_state(self);
+}
+
+void rxx_ostream_clear(std::ostream &self) noexcept {
+  self.clear();
+}
+
+int rxx_ostream_write(std::ostream &self, const char *buf, size_t len) noexcept {
+  try {
+    self.write(buf, std::streamsize(len));
+  } catch (...) {
+  }
+  return stream_state(self);
+}
+
+int rxx_ostream_flush(std::ostream &self) noexcept {
+  try {
+    self.flush();
+  } catch (...) {
+  }
+  return stream_state(self);
+}
+
+void rxx_stringstream_new(const char *buf, size_t len, std::unique_ptr<std::stringstream> *out) noexcept {
+  new (out) std::unique_ptr<std::stringstream>(new std::stringstream(std::string(buf, len)));
+}
+
+std::istream &rxx_stringstream_istream(std::stringstream &self) noexcept {
+  return self;
+}
+
+std::ostream &rxx_stringstream_ostream(std::stringstream &self) noexcept {
+  return self;
+}
+
+void rxx_stringstream_str(const std::stringstream &self, std::unique_ptr<std::string> *out) noexcept {
+  new (out) std::unique_ptr<std::string>(new std::string(self.str()));
+}
+
+#if __cplusplus >= 201703L
+static_assert(sizeof(std::any) == 2 * sizeof(void *) && alignof(std::any) == alignof(void *),
+              "unsupported layout of std::any");
+
+void rxx_any_new(std::any *out) noexcept {
+  new (out) std::any();
+}
+
+void rxx_any_destroy(std::any &self) noexcept {
+  destroy(&self);
+}
+
+void rxx_any_clone(const std::any &self, std::any *out) noexcept {
+  new (out) std::any(self);
+}
+
+bool rxx_any_has_value(const std::any &self) noexcept {
+  return self.has_value();
+}
+
+const std::type_info &rxx_any_type(const std::any &self) noexcept {
+  return self.type();
+}
+
+void rxx_any_reset(std::any &self) noexcept {
+  self.reset();
+}
+
+static_assert(sizeof(std::filesystem::path) == 5 * sizeof(void *) &&
+                  alignof(std::filesystem::path) == alignof(void *),
+              "unsupported layout of std::filesystem::path");
+
+void rxx_path_new(const char *ptr, size_t len, std::unique_ptr<std::filesystem::path> *out) noexcept {
+  new (out) std::unique_ptr<std::filesystem::path>(new std::filesystem::path(std::string(ptr, len)));
+}
+
+const char *rxx_path_native(const std::filesystem::path &self, size_t *len) noexcept {
+  *len = self.native().size();
+  return self.c_str();
+}
+
+void rxx_path_join(const std::filesystem::path &self, const char *ptr, size_t len, std::unique_ptr<std::filesystem::path> *out) noexcept {
+  new (out) std::unique_ptr<std::filesystem::path>(new std::filesystem::path(self / std::string(ptr, len)));
+}
+
+void rxx_vector_path_push(std::vector<std::filesystem::path> &self, const char *ptr, size_t len) noexcept {
+  self.emplace_back(std::string(ptr, len));
+}
+#endif
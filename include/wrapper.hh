@@ -2,7 +2,47 @@
 
 #include <memory>
 #include <iostream>
+#include <sstream>
 #include <vector>
+#include <unordered_map>
+#include <map>
+#include <set>
+#include <unordered_set>
+#include <deque>
+#include <list>
+#include <iterator>
+#include <functional>
+#include <system_error>
+#include <exception>
+#include <tuple>
+#include <array>
+#include <chrono>
+#include <complex>
+#include <utility>
+#include <cstddef>
+#include <algorithm>
+#include <type_traits>
+#include <typeinfo>
+#include <limits>
+
+#if __cplusplus >= 201703L
+#include <any>
+#include <filesystem>
+#include <optional>
+#include <variant>
+#endif
+
+// Names the Rust type `T` exported by `genrs_rust_type!(link_name, T)` as an
+// opaque C++ type, std::unique_ptr<T> runs the Rust destructor. Must be used
+// in the global namespace.
+#define RXX_RUST_TYPE(T, link_name)                                     \
+  struct T;                                                             \
+  extern "C" void link_name##_drop(T *self) noexcept;                   \
+  namespace std {                                                       \
+  template <> struct default_delete<T> {                                \
+    void operator()(T *self) const noexcept { link_name##_drop(self); } \
+  };                                                                    \
+  }
 
 namespace rxx {
 
@@ -17,6 +57,93 @@ void shared_ptr_clone(const std::shared_ptr<T> &self, std::shared_ptr<T> *out)
   new (out) std::shared_ptr<T>(self);
 }
 
+// `value` is moved from and destroyed, genc_make_shared checks that T is
+// move and default constructible
+template<typename T>
+void shared_ptr_new(void *value, std::shared_ptr<T> *out)
+{
+  T *val = static_cast<T *>(value);
+  new (out) std::shared_ptr<T>(std::make_shared<T>(std::move(*val)));
+  destroy(val);
+}
+
+template<typename T>
+void shared_ptr_new_default(std::shared_ptr<T> *out)
+{
+  new (out) std::shared_ptr<T>(std::make_shared<T>());
+}
+
+template<typename T, typename U>
+void shared_ptr_alias(const std::shared_ptr<T> &self, U *ptr, std::shared_ptr<U> *out)
+{
+  new (out) std::shared_ptr<U>(self, ptr);
+}
+
+template<typename T, typename U>
+void shared_ptr_dynamic_cast(const std::shared_ptr<T> &self, std::shared_ptr<U> *out)
+{
+  new (out) std::shared_ptr<U>(std::dynamic_pointer_cast<U>(self));
+}
+
+template<typename T, typename U>
+void shared_ptr_static_cast(const std::shared_ptr<T> &self, std::shared_ptr<U> *out)
+{
+  new (out) std::shared_ptr<U>(std::static_pointer_cast<U>(self));
+}
+
+template<typename T, typename U>
+void unique_ptr_dynamic_cast(std::unique_ptr<T> &self, std::unique_ptr<U> *out)
+{
+  U *ptr = dynamic_cast<U *>(self.get());
+  if (ptr)
+    self.release();
+  new (out) std::unique_ptr<U>(ptr);
+}
+
+template<typename T, typename U>
+void unique_ptr_static_cast(std::unique_ptr<T> &self, std::unique_ptr<U> *out)
+{
+  new (out) std::unique_ptr<U>(static_cast<U *>(self.release()));
+}
+
+template<typename T>
+void shared_ptr_atomic_load(const std::shared_ptr<T> &self, std::shared_ptr<T> *out)
+{
+  new (out) std::shared_ptr<T>(std::atomic_load(&self));
+}
+
+template<typename T>
+void shared_ptr_atomic_store(std::shared_ptr<T> &self, std::shared_ptr<T> &value)
+{
+  std::atomic_store(&self, std::move(value));
+  destroy(&value);
+}
+
+template<typename T>
+void shared_ptr_atomic_exchange(std::shared_ptr<T> &self, std::shared_ptr<T> &value, std::shared_ptr<T> *out)
+{
+  new (out) std::shared_ptr<T>(std::atomic_exchange(&self, std::move(value)));
+  destroy(&value);
+}
+
+template<typename T>
+bool shared_ptr_atomic_compare_exchange(std::shared_ptr<T> &self, std::shared_ptr<T> &expected, const std::shared_ptr<T> &desired)
+{
+  return std::atomic_compare_exchange_strong(&self, &expected, desired);
+}
+
+template<typename T>
+bool shared_from_this(const T &self, std::shared_ptr<T> *out)
+{
+  try {
+    new (out) std::shared_ptr<T>(std::const_pointer_cast<T>(self.shared_from_this()));
+    return true;
+  } catch (const std::bad_weak_ptr &) {
+    new (out) std::shared_ptr<T>();
+    return false;
+  }
+}
+
 template<typename T>
 void weak_ptr_upgrade(const std::weak_ptr<T> &self, std::shared_ptr<T> *out)
 {
@@ -63,6 +190,472 @@ void vector_pop_back(std::vector<T> &self, T *out) {
   self.pop_back();
 }
 
+// what CxxPair<A, B> assumes std::pair<A, B> looks like
+template<typename A, typename B>
+struct pair_layout {
+  A first;
+  B second;
+};
+
+template<typename A, typename B>
+constexpr bool pair_layout_ok() {
+  using P = std::pair<A, B>;
+  using L = pair_layout<A, B>;
+  return offsetof(P, first) == offsetof(L, first) &&
+         offsetof(P, second) == offsetof(L, second) &&
+         sizeof(P) == sizeof(L) && alignof(P) == alignof(L);
+}
+
+// what CxxArray<T, N> assumes std::array<T, N> looks like
+template<typename T, size_t N>
+constexpr bool array_layout_ok() {
+  return sizeof(std::array<T, N>) == N * sizeof(T) &&
+         alignof(std::array<T, N>) == alignof(T) &&
+         std::is_trivially_copyable<std::array<T, N>>::value;
+}
+
+// calls `f`, a thrown std::system_error is stored in `ec` for Rust instead
+template<typename F>
+auto catch_system_error(std::error_code &ec, F f) -> decltype(f()) {
+  try {
+    return f();
+  } catch (const std::system_error &e) {
+    ec = e.code();
+    return decltype(f())();
+  }
+}
+
+// what CxxComplex<T> assumes std::complex<T> looks like
+template<typename T>
+constexpr bool complex_layout_ok() {
+  return sizeof(std::complex<T>) == 2 * sizeof(T) &&
+         alignof(std::complex<T>) == alignof(T) &&
+         std::is_trivially_copyable<std::complex<T>>::value;
+}
+
+// what CxxDuration<NUM, DEN> assumes a std::chrono::duration looks like
+template<typename D>
+constexpr bool duration_layout_ok(intmax_t num, intmax_t den) {
+  using rep = typename D::rep;
+  return std::is_integral<rep>::value && std::is_signed<rep>::value &&
+         sizeof(rep) == 8 && sizeof(D) == 8 &&
+         std::is_trivially_copyable<D>::value &&
+         D::period::num == num && D::period::den == den;
+}
+
+// size of a C struct with members `Ts...` in order
+template<typename... Ts>
+constexpr size_t c_struct_size() {
+  size_t sizes[] = {sizeof(Ts)...};
+  size_t aligns[] = {alignof(Ts)...};
+  size_t size = 0, align = 1;
+  for (size_t i = 0; i < sizeof...(Ts); i++) {
+    size = (size + aligns[i] - 1) / aligns[i] * aligns[i] + sizes[i];
+    align = std::max(align, aligns[i]);
+  }
+  return (size + align - 1) / align * align;
+}
+
+// what CxxTuple<(Ts, ...)> assumes the size and alignment of T are
+template<typename T, typename... Ts>
+constexpr bool tuple_layout_ok() {
+  return std::is_same<T, std::tuple<Ts...>>::value &&
+         sizeof(T) == c_struct_size<Ts...>() &&
+         alignof(T) == std::max({alignof(Ts)...});
+}
+
+template<typename T, typename... Ts>
+void tuple_new(T *out, Ts &...values) {
+  new (out) T(std::move(values)...);
+  (void)std::initializer_list<int>{(destroy(&values), 0)...};
+}
+
+template<typename T, size_t... I, typename... Ts>
+void tuple_take_impl(T &self, std::index_sequence<I...>, Ts *...out) {
+  (void)std::initializer_list<int>{(new (out) Ts(std::move(std::get<I>(self))), 0)...};
+  destroy(&self);
+}
+
+// moves every element out and destroys `self`
+template<typename T, typename... Ts>
+void tuple_take(T &self, Ts *...out) {
+  tuple_take_impl(self, std::index_sequence_for<Ts...>(), out...);
+}
+
+// CxxUnorderedMap and friends keep C++ iterators in a RawIter
+template<typename It>
+constexpr bool raw_iter_ok() {
+  return sizeof(It) <= 2 * sizeof(void *) && alignof(It) <= alignof(void *) &&
+         std::is_trivially_copyable<It>::value &&
+         std::is_trivially_destructible<It>::value;
+}
+
+template<typename M>
+const typename M::mapped_type *map_get(const M &self, const typename M::key_type &key) {
+  auto it = self.find(key);
+  return it == self.end() ? nullptr : &it->second;
+}
+
+template<typename M>
+typename M::mapped_type *map_get_mut(M &self, const typename M::key_type &key) {
+  auto it = self.find(key);
+  return it == self.end() ? nullptr : &it->second;
+}
+
+// moves the replaced value to `old` and returns true if `key` was present
+template<typename M>
+bool map_insert(M &self, const typename M::key_type &key, typename M::mapped_type &value, typename M::mapped_type *old) {
+  auto it = self.find(key);
+  bool found = it != self.end();
+  if (found) {
+    new (old) typename M::mapped_type(std::move(it->second));
+    it->second = std::move(value);
+  } else {
+    self.emplace(key, std::move(value));
+  }
+  destroy(&value);
+  return found;
+}
+
+template<typename M>
+bool map_remove(M &self, const typename M::key_type &key, typename M::mapped_type *out) {
+  auto it = self.find(key);
+  if (it == self.end())
+    return false;
+  new (out) typename M::mapped_type(std::move(it->second));
+  self.erase(it);
+  return true;
+}
+
+template<typename C>
+void iter_new(const C &self, void *it) {
+  new (it) typename C::const_iterator(self.begin());
+}
+
+// returns null at the end, otherwise the value and its key of the current
+// element, then advances
+template<typename M>
+const typename M::mapped_type *map_iter_next(const M &self, void *it, const typename M::key_type **key) {
+  auto &i = *static_cast<typename M::const_iterator *>(it);
+  if (i == self.end())
+    return nullptr;
+  *key = &i->first;
+  return &(i++)->second;
+}
+
+// what CxxFunction assumes about std::function, libstdc++ keeps small
+// targets inline only if they are trivially copyable, so it can be moved
+// by Rust. libc++ has the same size but may point into itself.
+template<typename F>
+constexpr bool function_layout_ok() {
+#ifndef __GLIBCXX__
+  static_assert(sizeof(F) == 0, "CxxFunction needs the std::function of libstdc++");
+#endif
+  return sizeof(F) == 4 * sizeof(void *) && alignof(F) == alignof(void *);
+}
+
+// target of a std::function made from a Rust closure, copies share `ctx`
+// which is freed by Rust when the last one is gone
+template<typename R, typename... Args>
+struct rust_function {
+  using call_t = void (*)(void *, Args..., R *);
+
+  std::shared_ptr<void> ctx;
+  call_t call;
+
+  R operator()(Args... args) const {
+    union slot {
+      R value;
+      slot() {}
+      ~slot() {}
+    } ret;
+    call(ctx.get(), args..., &ret.value);
+    R out(std::move(ret.value));
+    ret.value.~R();
+    return out;
+  }
+};
+
+template<typename... Args>
+struct rust_function<void, Args...> {
+  using call_t = void (*)(void *, Args...);
+
+  std::shared_ptr<void> ctx;
+  call_t call;
+
+  void operator()(Args... args) const {
+    call(ctx.get(), args...);
+  }
+};
+
+// CxxCallback, a Rust closure borrowed for the duration of a call
+template<typename Sig>
+struct callback;
+
+// `call` returns false if the closure panicked, the panic resumes once the
+// C++ call returns to Rust
+template<typename R, typename... Args>
+struct callback<R(Args...)> {
+  bool (*call)(void *, Args..., R *);
+  void *ctx;
+
+  R operator()(Args... args) const {
+    union slot {
+      R value;
+      slot() {}
+      ~slot() {}
+    } ret;
+    if (!call(ctx, args..., &ret.value))
+      return panicked(std::is_default_constructible<R>());
+    R out(std::move(ret.value));
+    ret.value.~R();
+    return out;
+  }
+
+private:
+  static R panicked(std::true_type) { return R(); }
+
+  [[noreturn]] static R panicked(std::false_type) { std::terminate(); }
+};
+
+template<typename... Args>
+struct callback<void(Args...)> {
+  bool (*call)(void *, Args..., void *);
+  void *ctx;
+
+  void operator()(Args... args) const {
+    char unit;
+    call(ctx, args..., &unit);
+  }
+};
+
+// CxxCallbackHandle, only the leading members are read
+template<typename Sig>
+struct c_callback;
+
+template<typename R, typename... Args>
+struct c_callback<R(Args...)> {
+  using fn_t = R (*)(void *, Args...);
+
+  fn_t call;
+  void *ctx;
+};
+
+// push and pop for std::deque and std::list, `value` is moved from and
+// destroyed, popped elements are moved to `out`
+template<typename S>
+void seq_push_back(S &self, typename S::value_type &value) {
+  self.push_back(std::move(value));
+  destroy(&value);
+}
+
+template<typename S>
+void seq_push_front(S &self, typename S::value_type &value) {
+  self.push_front(std::move(value));
+  destroy(&value);
+}
+
+template<typename S>
+void seq_pop_back(S &self, typename S::value_type *out) {
+  new (out) typename S::value_type(std::move(self.back()));
+  self.pop_back();
+}
+
+template<typename S>
+void seq_pop_front(S &self, typename S::value_type *out) {
+  new (out) typename S::value_type(std::move(self.front()));
+  self.pop_front();
+}
+
+template<typename S>
+const typename S::value_type *seq_front(const S &self) {
+  return self.empty() ? nullptr : &self.front();
+}
+
+template<typename S>
+const typename S::value_type *seq_back(const S &self) {
+  return self.empty() ? nullptr : &self.back();
+}
+
+template<typename L>
+void list_splice(L &self, size_t pos, L &other) {
+  auto it = pos == self.size() ? self.end() : std::next(self.begin(), pos);
+  self.splice(it, other);
+}
+
+template<typename C>
+void range_new(const C &self, void *begin_it, void *end_it) {
+  new (begin_it) typename C::const_iterator(self.begin());
+  new (end_it) typename C::const_iterator(self.end());
+}
+
+// both return null once `it` meets `end`, otherwise the element taken from
+// the front or the back of the range
+template<typename C>
+const typename C::value_type *range_next(void *it, const void *end) {
+  auto &i = *static_cast<typename C::const_iterator *>(it);
+  if (i == *static_cast<const typename C::const_iterator *>(end))
+    return nullptr;
+  return &*(i++);
+}
+
+template<typename C>
+const typename C::value_type *range_next_back(const void *it, void *end) {
+  auto &e = *static_cast<typename C::const_iterator *>(end);
+  if (*static_cast<const typename C::const_iterator *>(it) == e)
+    return nullptr;
+  return &*(--e);
+}
+
+template<typename S>
+bool set_insert(S &self, typename S::value_type &value) {
+  bool inserted = self.insert(std::move(value)).second;
+  destroy(&value);
+  return inserted;
+}
+
+// returns null at the end, otherwise the current element, then advances
+template<typename S>
+const typename S::value_type *set_iter_next(const S &self, void *it) {
+  auto &i = *static_cast<typename S::const_iterator *>(it);
+  if (i == self.end())
+    return nullptr;
+  return &*(i++);
+}
+
+// `*_kind` is 0 for unbounded, 1 for included and 2 for excluded, an empty
+// range is returned if start is past end
+template<typename M>
+void map_range(const M &self, uint8_t start_kind, const typename M::key_type *start,
+               uint8_t end_kind, const typename M::key_type *end, void *begin_it, void *end_it) {
+  using It = typename M::const_iterator;
+  It b = start_kind == 0 ? self.begin() : start_kind == 1 ? self.lower_bound(*start) : self.upper_bound(*start);
+  It e = end_kind == 0 ? self.end() : end_kind == 1 ? self.upper_bound(*end) : self.lower_bound(*end);
+  if (start_kind && end_kind) {
+    auto comp = self.key_comp();
+    bool equal = !comp(*start, *end) && !comp(*end, *start);
+    if (comp(*end, *start) || (equal && (start_kind == 2 || end_kind == 2)))
+      b = e;
+  }
+  new (begin_it) It(b);
+  new (end_it) It(e);
+}
+
+template<typename M>
+const typename M::mapped_type *map_range_next(const M &, void *it, const void *end, const typename M::key_type **key) {
+  using It = typename M::const_iterator;
+  auto &i = *static_cast<It *>(it);
+  if (i == *static_cast<const It *>(end))
+    return nullptr;
+  *key = &i->first;
+  return &(i++)->second;
+}
+
+template<typename M>
+const typename M::mapped_type *map_last(const M &self, const typename M::key_type **key) {
+  if (self.empty())
+    return nullptr;
+  auto it = self.rbegin();
+  *key = &it->first;
+  return &it->second;
+}
+
+// first value inserted with `key`, `find` may return any of them
+template<typename M>
+const typename M::mapped_type *multimap_get(const M &self, const typename M::key_type &key) {
+  auto it = self.lower_bound(key);
+  return it == self.end() || self.key_comp()(key, it->first) ? nullptr : &it->second;
+}
+
+template<typename M>
+typename M::mapped_type *multimap_get_mut(M &self, const typename M::key_type &key) {
+  auto it = self.lower_bound(key);
+  return it == self.end() || self.key_comp()(key, it->first) ? nullptr : &it->second;
+}
+
+template<typename M>
+void multimap_insert(M &self, const typename M::key_type &key, typename M::mapped_type &value) {
+  self.emplace(key, std::move(value));
+  destroy(&value);
+}
+
+#if __cplusplus >= 201703L
+template<typename T>
+void any_new(T &value, std::any *out) {
+  new (out) std::any(std::move(value));
+  destroy(&value);
+}
+
+// what CxxOptional<T> assumes std::optional<T> looks like
+template<typename T>
+struct optional_layout {
+  T value;
+  bool engaged;
+};
+
+template<typename T>
+constexpr bool optional_layout_ok() {
+  return sizeof(std::optional<T>) == sizeof(optional_layout<T>) &&
+         alignof(std::optional<T>) == alignof(optional_layout<T>);
+}
+
+template<typename T>
+void optional_new(T &value, std::optional<T> *out) {
+  new (out) std::optional<T>(std::move(value));
+  destroy(&value);
+}
+
+template<typename T>
+T *optional_get(std::optional<T> &self) {
+  return self ? &*self : nullptr;
+}
+
+template<typename T>
+const T *optional_get(const std::optional<T> &self) {
+  return self ? &*self : nullptr;
+}
+
+// what CxxVariant<E> assumes std::variant<Ts...> looks like
+template<typename... Ts>
+struct variant_layout {
+  alignas(Ts...) unsigned char data[std::max({sizeof(Ts)...})];
+  unsigned char index;
+};
+
+template<typename V, typename... Ts>
+constexpr bool variant_layout_ok() {
+  return std::is_same<V, std::variant<Ts...>>::value &&
+         sizeof(V) == sizeof(variant_layout<Ts...>) &&
+         alignof(V) == alignof(variant_layout<Ts...>);
+}
+
+template<typename V>
+size_t variant_index(const V &self) {
+  return self.valueless_by_exception() ? SIZE_MAX : self.index();
+}
+
+template<size_t I, typename V, typename T>
+void variant_new(T &value, V *out) {
+  new (out) V(std::in_place_index<I>, std::move(value));
+  destroy(&value);
+}
+
+template<size_t I, typename V, typename T>
+void variant_emplace(V &self, T &value) {
+  self.template emplace<I>(std::move(value));
+  destroy(&value);
+}
+
+template<typename T>
+bool optional_take(std::optional<T> &self, T *out) {
+  if (!self)
+    return false;
+  new (out) T(std::move(*self));
+  self.reset();
+  return true;
+}
+#endif
+
 } // namespace rxx
 
 extern "C" {
@@ -73,4 +666,48 @@ extern "C" {
   void rxx_string_clear(std::string &self) noexcept;
   void rxx_string_reserve(std::string &self, size_t n) noexcept;
   void rxx_string_push(std::string &self, const uint8_t *ptr, size_t len) noexcept;
+
+  void rxx_shared_ptr_alias(const std::shared_ptr<void> &self, void *ptr, std::shared_ptr<void> *out) noexcept;
+  void rxx_shared_ptr_from_raw(void *ptr, void (*deleter)(void *), std::shared_ptr<void> *out) noexcept;
+  void rxx_shared_ptr_void_delete(std::shared_ptr<void> &self) noexcept;
+  void rxx_shared_ptr_void_clone(const std::shared_ptr<void> &self, std::shared_ptr<void> *out) noexcept;
+  size_t rxx_shared_ptr_use_count(const std::shared_ptr<void> &self) noexcept;
+
+  const char *rxx_type_info_name(const std::type_info &self) noexcept;
+  bool rxx_type_info_eq(const std::type_info &self, const std::type_info &other) noexcept;
+
+  std::chrono::steady_clock::time_point rxx_steady_clock_now() noexcept;
+
+  std::error_code rxx_error_code_new() noexcept;
+  const char *rxx_error_code_category_name(const std::error_code &self) noexcept;
+  void rxx_error_code_message(const std::error_code &self, std::unique_ptr<std::string> *out) noexcept;
+  bool rxx_error_code_is_os_error(const std::error_code &self) noexcept;
+
+  int rxx_istream_state(const std::istream &self) noexcept;
+  void rxx_istream_clear(std::istream &self) noexcept;
+  size_t rxx_istream_read(std::istream &self, char *buf, size_t len, int *state) noexcept;
+  const char *rxx_istream_fill_buf(std::istream &self, size_t *len, int *state) noexcept;
+  void rxx_istream_consume(std::istream &self, size_t n) noexcept;
+  int rxx_ostream_state(const std::ostream &self) noexcept;
+  void rxx_ostream_clear(std::ostream &self) noexcept;
+  int rxx_ostream_write(std::ostream &self, const char *buf, size_t len) noexcept;
+  int rxx_ostream_flush(std::ostream &self) noexcept;
+  void rxx_stringstream_new(const char *buf, size_t len, std::unique_ptr<std::stringstream> *out) noexcept;
+  std::istream &rxx_stringstream_istream(std::stringstream &self) noexcept;
+  std::ostream &rxx_stringstream_ostream(std::stringstream &self) noexcept;
+  void rxx_stringstream_str(const std::stringstream &self, std::unique_ptr<std::string> *out) noexcept;
+
+#if __cplusplus >= 201703L
+  void rxx_any_new(std::any *out) noexcept;
+  void rxx_any_destroy(std::any &self) noexcept;
+  void rxx_any_clone(const std::any &self, std::any *out) noexcept;
+  bool rxx_any_has_value(const std::any &self) noexcept;
+  const std::type_info &rxx_any_type(const std::any &self) noexcept;
+  void rxx_any_reset(std::any &self) noexcept;
+
+  void rxx_path_new(const char *ptr, size_t len, std::unique_ptr<std::filesystem::path> *out) noexcept;
+  const char *rxx_path_native(const std::filesystem::path &self, size_t *len) noexcept;
+  void rxx_path_join(const std::filesystem::path &self, const char *ptr, size_t len, std::unique_ptr<std::filesystem::path> *out) noexcept;
+  void rxx_vector_path_push(std::vector<std::filesystem::path> &self, const char *ptr, size_t len) noexcept;
+#endif
 }
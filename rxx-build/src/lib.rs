@@ -6,63 +6,63 @@ use serde_json::json;
 static TPL_RET_OBJECT_FN: &str = r#"
 extern "C" void {{name}}({{{decl_link_args}}}{{{ret_type}}} *__ret) noexcept {
     {{{ret_type}}} (*__func)({{{decl_args}}}) = {{{c_fn}}};
-    new (__ret) ({{{ret_type}}})(__func({{{call_args}}}));
+    new (__ret) ({{{ret_type}}})({{{call}}});
 }
 "#;
 
 static TPL_RET_ATOMIC_FN: &str = r#"
 extern "C" {{{ret_type}}} {{name}}({{{decl_link_args}}}) noexcept {
     {{{ret_type}}} (*__func)({{{decl_args}}}) = {{{c_fn}}};
-    return __func({{{call_args}}});
+    return {{{call}}};
 }
 "#;
 
 static TPL_VOID_FN: &str = r#"
 extern "C" void {{name}}({{{decl_link_args}}}) noexcept {
     void (*__func)({{{decl_args}}}) = {{{c_fn}}};
-    __func({{{call_args}}});
+    {{{call}}};
 }
 "#;
 
 static TPL_RET_OBJECT_MEMFN: &str = r#"
 extern "C" void {{name}}({{{cls}}} const &self{{{decl_link_args}}}{{{ret_type}}} *__ret) noexcept {
     {{{ret_type}}} ({{{cls}}}::*__func)({{{decl_args}}}) const = {{{c_fn}}};
-    new (__ret) {{{ret_type}}}((self.*__func)({{{call_args}}}));
+    new (__ret) {{{ret_type}}}({{{call}}});
 }
 "#;
 
 static TPL_RET_ATOMIC_MEMFN: &str = r#"
 extern "C" {{{ret_type}}} {{name}}({{{cls}}} const &self{{{decl_link_args}}}) noexcept {
     {{{ret_type}}} ({{{cls}}}::*__func)({{{decl_args}}}) const = {{{c_fn}}};
-    return (self.*__func)({{{call_args}}});
+    return {{{call}}};
 }
 "#;
 
 static TPL_VOID_MEMFN: &str = r#"
 extern "C" void {{name}}({{{cls}}} const &self{{{decl_link_args}}}) noexcept {
     void ({{{cls}}}::*__func)({{{decl_args}}}) const = {{{c_fn}}};
-    (self.*__func)({{{call_args}}});
+    {{{call}}};
 }
 "#;
 
 static TPL_RET_OBJECT_MEMFN_MUT: &str = r#"
 extern "C" void {{name}}({{{cls}}} &self{{{decl_link_args}}}{{{ret_type}}} *__ret) noexcept {
     {{{ret_type}}} ({{{cls}}}::*__func)({{{decl_args}}}) = {{{c_fn}}};
-    new (__ret) {{{ret_type}}}((self.*__func)({{{call_args}}}));
+    new (__ret) {{{ret_type}}}({{{call}}});
 }
 "#;
 
 static TPL_RET_ATOMIC_MEMFN_MUT: &str = r#"
 extern "C" {{{ret_type}}} {{name}}({{{cls}}} &self{{{decl_link_args}}}) noexcept {
     {{{ret_type}}} ({{{cls}}}::*__func)({{{decl_args}}}) = {{{c_fn}}};
-    return (self.*__func)({{{call_args}}});
+    return {{{call}}};
 }
 "#;
 
 static TPL_VOID_MEMFN_MUT: &str = r#"
 extern "C" void {{name}}({{{cls}}} &self{{{decl_link_args}}}) noexcept {
     void ({{{cls}}}::*__func)({{{decl_args}}}) = {{{c_fn}}};
-    (self.*__func)({{{call_args}}});
+    {{{call}}};
 }
 "#;
 
@@ -82,6 +82,19 @@ extern "C" void {{name}}_clone(const {{{c_tp}}} &self, {{{c_tp}}} *out) noexcept
 }
 "#;
 
+static TPL_MAKE_SHARED: &str = r#"
+static_assert(std::is_move_constructible<{{{c_tp}}}::element_type>::value, "{{{c_tp}}} element is not move constructible");
+static_assert(std::is_default_constructible<{{{c_tp}}}::element_type>::value, "{{{c_tp}}} element is not default constructible");
+
+extern "C" void {{name}}_new(void *value, {{{c_tp}}} *out) noexcept {
+    rxx::shared_ptr_new(value, out);
+}
+
+extern "C" void {{name}}_new_default({{{c_tp}}} *out) noexcept {
+    rxx::shared_ptr_new_default(out);
+}
+"#;
+
 static TPL_WEAK_PTR: &str = r#"
 extern "C" void {{name}}_delete({{{c_tp}}} &self) noexcept {
     rxx::destroy(&self);
@@ -100,6 +113,178 @@ extern "C"  void {{name}}_downgrade(const {{{c_shared_tp}}} &self, {{{c_tp}}} *o
 }
 "#;
 
+static TPL_ATOMIC_SHARED_PTR: &str = r#"
+extern "C" void {{name}}_atomic_load(const {{{c_tp}}} &self, {{{c_tp}}} *out) noexcept {
+    rxx::shared_ptr_atomic_load(self, out);
+}
+
+extern "C" void {{name}}_atomic_store({{{c_tp}}} &self, {{{c_tp}}} &value) noexcept {
+    rxx::shared_ptr_atomic_store(self, value);
+}
+
+extern "C" void {{name}}_atomic_exchange({{{c_tp}}} &self, {{{c_tp}}} &value, {{{c_tp}}} *out) noexcept {
+    rxx::shared_ptr_atomic_exchange(self, value, out);
+}
+
+extern "C" bool {{name}}_atomic_compare_exchange({{{c_tp}}} &self, {{{c_tp}}} &expected, const {{{c_tp}}} &desired) noexcept {
+    return rxx::shared_ptr_atomic_compare_exchange(self, expected, desired);
+}
+"#;
+
+static TPL_POINTER_CAST: &str = r#"
+extern "C" void {{name}}_shared_downcast(const std::shared_ptr<{{{c_base_tp}}}> &self, std::shared_ptr<{{{c_derived_tp}}}> *out) noexcept {
+    rxx::shared_ptr_dynamic_cast(self, out);
+}
+
+extern "C" void {{name}}_shared_static_downcast(const std::shared_ptr<{{{c_base_tp}}}> &self, std::shared_ptr<{{{c_derived_tp}}}> *out) noexcept {
+    rxx::shared_ptr_static_cast(self, out);
+}
+
+extern "C" void {{name}}_shared_upcast(const std::shared_ptr<{{{c_derived_tp}}}> &self, std::shared_ptr<{{{c_base_tp}}}> *out) noexcept {
+    rxx::shared_ptr_static_cast(self, out);
+}
+
+extern "C" void {{name}}_unique_downcast(std::unique_ptr<{{{c_base_tp}}}> &self, std::unique_ptr<{{{c_derived_tp}}}> *out) noexcept {
+    rxx::unique_ptr_dynamic_cast(self, out);
+}
+
+extern "C" void {{name}}_unique_static_downcast(std::unique_ptr<{{{c_base_tp}}}> &self, std::unique_ptr<{{{c_derived_tp}}}> *out) noexcept {
+    rxx::unique_ptr_static_cast(self, out);
+}
+
+extern "C" void {{name}}_unique_upcast(std::unique_ptr<{{{c_derived_tp}}}> &self, std::unique_ptr<{{{c_base_tp}}}> *out) noexcept {
+    rxx::unique_ptr_static_cast(self, out);
+}
+"#;
+
+static TPL_SHARED_FROM_THIS: &str = r#"
+extern "C" bool {{name}}_shared_from_this(const {{{c_tp}}} &self, std::shared_ptr<{{{c_tp}}}> *out) noexcept {
+    return rxx::shared_from_this(self, out);
+}
+"#;
+
+static TPL_TYPE_INFO: &str = r#"
+extern "C" const std::type_info &{{name}}_type_info() noexcept {
+    return typeid({{{c_tp}}});
+}
+"#;
+
+static TPL_ANY: &str = r#"
+extern "C" const std::type_info &{{name}}_type_info() noexcept {
+    return typeid({{{c_tp}}});
+}
+
+extern "C" void {{name}}_any_new({{{c_tp}}} &value, std::any *out) noexcept {
+    rxx::any_new(value, out);
+}
+
+extern "C" const {{{c_tp}}} *{{name}}_any_cast(const std::any &self) noexcept {
+    return std::any_cast<{{{c_tp}}}>(&self);
+}
+
+extern "C" {{{c_tp}}} *{{name}}_any_cast_mut(std::any &self) noexcept {
+    return std::any_cast<{{{c_tp}}}>(&self);
+}
+"#;
+
+static TPL_OPTIONAL: &str = r#"
+static_assert(rxx::optional_layout_ok<{{{c_tp}}}>(), "unsupported layout of std::optional<{{{c_tp}}}>");
+
+extern "C" void {{name}}_delete(std::optional<{{{c_tp}}}> &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void {{name}}_new_none(std::optional<{{{c_tp}}}> *out) noexcept {
+    new (out) std::optional<{{{c_tp}}}>();
+}
+
+extern "C" void {{name}}_new({{{c_tp}}} &value, std::optional<{{{c_tp}}}> *out) noexcept {
+    rxx::optional_new(value, out);
+}
+
+extern "C" const {{{c_tp}}} *{{name}}_get(const std::optional<{{{c_tp}}}> &self) noexcept {
+    return rxx::optional_get(self);
+}
+
+extern "C" {{{c_tp}}} *{{name}}_get_mut(std::optional<{{{c_tp}}}> &self) noexcept {
+    return rxx::optional_get(self);
+}
+
+extern "C" bool {{name}}_take(std::optional<{{{c_tp}}}> &self, {{{c_tp}}} *out) noexcept {
+    return rxx::optional_take(self, out);
+}
+"#;
+
+static TPL_VARIANT: &str = r#"
+static_assert(rxx::variant_layout_ok<{{{c_tp}}}{{#each alts}}, {{{this.tp}}}{{/each}}>(), "unsupported layout of {{{c_tp}}}");
+
+extern "C" void {{name}}_delete({{{c_tp}}} &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" size_t {{name}}_index(const {{{c_tp}}} &self) noexcept {
+    return rxx::variant_index(self);
+}
+{{#each alts}}
+
+extern "C" const {{{this.tp}}} *{{../name}}_get_if_{{this.name}}(const {{{../c_tp}}} &self) noexcept {
+    return std::get_if<{{@index}}>(&self);
+}
+
+extern "C" void {{../name}}_new_{{this.name}}({{{this.tp}}} &value, {{{../c_tp}}} *out) noexcept {
+    rxx::variant_new<{{@index}}>(value, out);
+}
+
+extern "C" void {{../name}}_emplace_{{this.name}}({{{../c_tp}}} &self, {{{this.tp}}} &value) noexcept {
+    rxx::variant_emplace<{{@index}}>(self, value);
+}
+{{/each}}"#;
+
+static TPL_PAIR: &str = r#"
+static_assert(rxx::pair_layout_ok<{{{c_first_tp}}}, {{{c_second_tp}}}>(), "unsupported layout of std::pair<{{{c_first_tp}}}, {{{c_second_tp}}}>");
+"#;
+
+static TPL_ARRAY: &str = r#"
+static_assert(rxx::array_layout_ok<{{{c_item_tp}}}, {{n}}>(), "unsupported layout of std::array<{{{c_item_tp}}}, {{n}}>");
+"#;
+
+static TPL_COMPLEX: &str = r#"
+static_assert(rxx::complex_layout_ok<{{{c_item_tp}}}>(), "unsupported layout of std::complex<{{{c_item_tp}}}>");
+"#;
+
+static TPL_DURATION: &str = r#"
+static_assert(rxx::duration_layout_ok<{{{c_tp}}}>({{num}}, {{den}}), "{{{c_tp}}} is not CxxDuration<{{num}}, {{den}}>");
+"#;
+
+static TPL_TIME_POINT: &str = r#"
+static_assert(rxx::duration_layout_ok<{{{c_tp}}}::duration>({{num}}, {{den}}), "{{{c_tp}}} is not counted in CxxDuration<{{num}}, {{den}}>");
+"#;
+
+static TPL_TUPLE: &str = r#"
+static_assert(rxx::tuple_layout_ok<{{{c_tp}}}{{#each c_tps}}, {{{this}}}{{/each}}>(), "unsupported layout of {{{c_tp}}}");
+
+extern "C" void {{name}}_delete({{{c_tp}}} &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void {{name}}_new({{#each c_tps}}{{{this}}} &v{{@index}}, {{/each}}{{{c_tp}}} *out) noexcept {
+    rxx::tuple_new(out{{#each c_tps}}, v{{@index}}{{/each}});
+}
+
+extern "C" void {{name}}_take({{{c_tp}}} &self{{#each c_tps}}, {{{this}}} *out{{@index}}{{/each}}) noexcept {
+    rxx::tuple_take(self{{#each c_tps}}, out{{@index}}{{/each}});
+}
+{{#each c_tps}}
+
+extern "C" const {{{this}}} &{{../name}}_get_{{@index}}(const {{{../c_tp}}} &self) noexcept {
+    return std::get<{{@index}}>(self);
+}
+
+extern "C" {{{this}}} *{{../name}}_get_mut_{{@index}}({{{../c_tp}}} &self) noexcept {
+    return &std::get<{{@index}}>(self);
+}
+{{/each}}"#;
+
 static TPL_VECTOR: &str = r#"
 extern "C" void {{name}}_delete(const {{{c_tp}}} &self) {
     rxx::destroy(&self);
@@ -126,6 +311,299 @@ extern "C" void {{name}}_pop_back({{{c_tp}}} &self, {{{c_item_tp}}} *out) {
 }
 "#;
 
+static TPL_UNORDERED_MAP: &str = r#"
+static_assert(rxx::raw_iter_ok<{{{c_tp}}}::const_iterator>(), "unsupported iterator of {{{c_tp}}}");
+
+extern "C" void {{name}}_unique_delete(std::unique_ptr<{{{c_tp}}}> &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void {{name}}_new(std::unique_ptr<{{{c_tp}}}> *out) noexcept {
+    new (out) std::unique_ptr<{{{c_tp}}}>(new {{{c_tp}}}());
+}
+
+extern "C" std::size_t {{name}}_size(const {{{c_tp}}} &self) noexcept {
+    return self.size();
+}
+
+extern "C" const {{{c_value_tp}}} *{{name}}_get(const {{{c_tp}}} &self, const {{{c_key_tp}}} &key) noexcept {
+    return rxx::map_get(self, key);
+}
+
+extern "C" {{{c_value_tp}}} *{{name}}_get_mut({{{c_tp}}} &self, const {{{c_key_tp}}} &key) noexcept {
+    return rxx::map_get_mut(self, key);
+}
+
+extern "C" bool {{name}}_insert({{{c_tp}}} &self, const {{{c_key_tp}}} &key, {{{c_value_tp}}} &value, {{{c_value_tp}}} *old) noexcept {
+    return rxx::map_insert(self, key, value, old);
+}
+
+extern "C" bool {{name}}_remove({{{c_tp}}} &self, const {{{c_key_tp}}} &key, {{{c_value_tp}}} *out) noexcept {
+    return rxx::map_remove(self, key, out);
+}
+
+extern "C" void {{name}}_clear({{{c_tp}}} &self) noexcept {
+    self.clear();
+}
+
+extern "C" void {{name}}_reserve({{{c_tp}}} &self, std::size_t n) noexcept {
+    self.reserve(n);
+}
+
+extern "C" void {{name}}_iter_new(const {{{c_tp}}} &self, void *it) noexcept {
+    rxx::iter_new(self, it);
+}
+
+extern "C" const {{{c_value_tp}}} *{{name}}_iter_next(const {{{c_tp}}} &self, void *it, const {{{c_key_tp}}} **key) noexcept {
+    return rxx::map_iter_next(self, it, key);
+}
+"#;
+
+static TPL_MAP: &str = r#"
+static_assert(rxx::raw_iter_ok<{{{c_tp}}}::const_iterator>(), "unsupported iterator of {{{c_tp}}}");
+
+extern "C" void {{name}}_unique_delete(std::unique_ptr<{{{c_tp}}}> &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void {{name}}_new(std::unique_ptr<{{{c_tp}}}> *out) noexcept {
+    new (out) std::unique_ptr<{{{c_tp}}}>(new {{{c_tp}}}());
+}
+
+extern "C" std::size_t {{name}}_size(const {{{c_tp}}} &self) noexcept {
+    return self.size();
+}
+
+{{#if multi}}
+extern "C" const {{{c_value_tp}}} *{{name}}_get(const {{{c_tp}}} &self, const {{{c_key_tp}}} &key) noexcept {
+    return rxx::multimap_get(self, key);
+}
+
+extern "C" {{{c_value_tp}}} *{{name}}_get_mut({{{c_tp}}} &self, const {{{c_key_tp}}} &key) noexcept {
+    return rxx::multimap_get_mut(self, key);
+}
+
+extern "C" void {{name}}_insert({{{c_tp}}} &self, const {{{c_key_tp}}} &key, {{{c_value_tp}}} &value) noexcept {
+    rxx::multimap_insert(self, key, value);
+}
+
+extern "C" std::size_t {{name}}_remove({{{c_tp}}} &self, const {{{c_key_tp}}} &key) noexcept {
+    return self.erase(key);
+}
+{{else}}
+extern "C" const {{{c_value_tp}}} *{{name}}_get(const {{{c_tp}}} &self, const {{{c_key_tp}}} &key) noexcept {
+    return rxx::map_get(self, key);
+}
+
+extern "C" {{{c_value_tp}}} *{{name}}_get_mut({{{c_tp}}} &self, const {{{c_key_tp}}} &key) noexcept {
+    return rxx::map_get_mut(self, key);
+}
+
+extern "C" bool {{name}}_insert({{{c_tp}}} &self, const {{{c_key_tp}}} &key, {{{c_value_tp}}} &value, {{{c_value_tp}}} *old) noexcept {
+    return rxx::map_insert(self, key, value, old);
+}
+
+extern "C" bool {{name}}_remove({{{c_tp}}} &self, const {{{c_key_tp}}} &key, {{{c_value_tp}}} *out) noexcept {
+    return rxx::map_remove(self, key, out);
+}
+{{/if}}
+extern "C" void {{name}}_clear({{{c_tp}}} &self) noexcept {
+    self.clear();
+}
+
+extern "C" void {{name}}_range(const {{{c_tp}}} &self, uint8_t start_kind, const {{{c_key_tp}}} *start, uint8_t end_kind, const {{{c_key_tp}}} *end, void *begin_it, void *end_it) noexcept {
+    rxx::map_range(self, start_kind, start, end_kind, end, begin_it, end_it);
+}
+
+extern "C" const {{{c_value_tp}}} *{{name}}_range_next(const {{{c_tp}}} &self, void *it, const void *end, const {{{c_key_tp}}} **key) noexcept {
+    return rxx::map_range_next(self, it, end, key);
+}
+
+extern "C" const {{{c_value_tp}}} *{{name}}_last(const {{{c_tp}}} &self, const {{{c_key_tp}}} **key) noexcept {
+    return rxx::map_last(self, key);
+}
+"#;
+
+static TPL_SET: &str = r#"
+static_assert(rxx::raw_iter_ok<{{{c_tp}}}::const_iterator>(), "unsupported iterator of {{{c_tp}}}");
+
+extern "C" void {{name}}_unique_delete(std::unique_ptr<{{{c_tp}}}> &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void {{name}}_new(std::unique_ptr<{{{c_tp}}}> *out) noexcept {
+    new (out) std::unique_ptr<{{{c_tp}}}>(new {{{c_tp}}}());
+}
+
+extern "C" std::size_t {{name}}_size(const {{{c_tp}}} &self) noexcept {
+    return self.size();
+}
+
+extern "C" bool {{name}}_contains(const {{{c_tp}}} &self, const {{{c_item_tp}}} &value) noexcept {
+    return self.find(value) != self.end();
+}
+
+extern "C" bool {{name}}_insert({{{c_tp}}} &self, {{{c_item_tp}}} &value) noexcept {
+    return rxx::set_insert(self, value);
+}
+
+extern "C" bool {{name}}_remove({{{c_tp}}} &self, const {{{c_item_tp}}} &value) noexcept {
+    return self.erase(value) != 0;
+}
+
+extern "C" void {{name}}_clear({{{c_tp}}} &self) noexcept {
+    self.clear();
+}
+{{#if unordered}}
+extern "C" void {{name}}_reserve({{{c_tp}}} &self, std::size_t n) noexcept {
+    self.reserve(n);
+}
+{{/if}}
+extern "C" void {{name}}_iter_new(const {{{c_tp}}} &self, void *it) noexcept {
+    rxx::iter_new(self, it);
+}
+
+extern "C" const {{{c_item_tp}}} *{{name}}_iter_next(const {{{c_tp}}} &self, void *it) noexcept {
+    return rxx::set_iter_next(self, it);
+}
+"#;
+
+static TPL_DEQUE: &str = r#"
+extern "C" void {{name}}_unique_delete(std::unique_ptr<{{{c_tp}}}> &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void {{name}}_new(std::unique_ptr<{{{c_tp}}}> *out) noexcept {
+    new (out) std::unique_ptr<{{{c_tp}}}>(new {{{c_tp}}}());
+}
+
+extern "C" std::size_t {{name}}_size(const {{{c_tp}}} &self) noexcept {
+    return self.size();
+}
+
+extern "C" const {{{c_item_tp}}} &{{name}}_get(const {{{c_tp}}} &self, size_t pos) noexcept {
+    return self[pos];
+}
+
+extern "C" {{{c_item_tp}}} &{{name}}_get_mut({{{c_tp}}} &self, size_t pos) noexcept {
+    return self[pos];
+}
+
+extern "C" void {{name}}_push_back({{{c_tp}}} &self, {{{c_item_tp}}} &value) noexcept {
+    rxx::seq_push_back(self, value);
+}
+
+extern "C" void {{name}}_push_front({{{c_tp}}} &self, {{{c_item_tp}}} &value) noexcept {
+    rxx::seq_push_front(self, value);
+}
+
+extern "C" void {{name}}_pop_back({{{c_tp}}} &self, {{{c_item_tp}}} *out) noexcept {
+    rxx::seq_pop_back(self, out);
+}
+
+extern "C" void {{name}}_pop_front({{{c_tp}}} &self, {{{c_item_tp}}} *out) noexcept {
+    rxx::seq_pop_front(self, out);
+}
+
+extern "C" void {{name}}_clear({{{c_tp}}} &self) noexcept {
+    self.clear();
+}
+"#;
+
+static TPL_LIST: &str = r#"
+static_assert(rxx::raw_iter_ok<{{{c_tp}}}::const_iterator>(), "unsupported iterator of {{{c_tp}}}");
+
+extern "C" void {{name}}_unique_delete(std::unique_ptr<{{{c_tp}}}> &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void {{name}}_new(std::unique_ptr<{{{c_tp}}}> *out) noexcept {
+    new (out) std::unique_ptr<{{{c_tp}}}>(new {{{c_tp}}}());
+}
+
+extern "C" std::size_t {{name}}_size(const {{{c_tp}}} &self) noexcept {
+    return self.size();
+}
+
+extern "C" const {{{c_item_tp}}} *{{name}}_front(const {{{c_tp}}} &self) noexcept {
+    return rxx::seq_front(self);
+}
+
+extern "C" const {{{c_item_tp}}} *{{name}}_back(const {{{c_tp}}} &self) noexcept {
+    return rxx::seq_back(self);
+}
+
+extern "C" void {{name}}_push_back({{{c_tp}}} &self, {{{c_item_tp}}} &value) noexcept {
+    rxx::seq_push_back(self, value);
+}
+
+extern "C" void {{name}}_push_front({{{c_tp}}} &self, {{{c_item_tp}}} &value) noexcept {
+    rxx::seq_push_front(self, value);
+}
+
+extern "C" void {{name}}_pop_back({{{c_tp}}} &self, {{{c_item_tp}}} *out) noexcept {
+    rxx::seq_pop_back(self, out);
+}
+
+extern "C" void {{name}}_pop_front({{{c_tp}}} &self, {{{c_item_tp}}} *out) noexcept {
+    rxx::seq_pop_front(self, out);
+}
+
+extern "C" void {{name}}_clear({{{c_tp}}} &self) noexcept {
+    self.clear();
+}
+
+extern "C" void {{name}}_splice({{{c_tp}}} &self, size_t pos, {{{c_tp}}} &other) noexcept {
+    rxx::list_splice(self, pos, other);
+}
+
+extern "C" void {{name}}_iter_new(const {{{c_tp}}} &self, void *begin_it, void *end_it) noexcept {
+    rxx::range_new(self, begin_it, end_it);
+}
+
+extern "C" const {{{c_item_tp}}} *{{name}}_iter_next(void *it, const void *end) noexcept {
+    return rxx::range_next<{{{c_tp}}}>(it, end);
+}
+
+extern "C" const {{{c_item_tp}}} *{{name}}_iter_next_back(const void *it, void *end) noexcept {
+    return rxx::range_next_back<{{{c_tp}}}>(it, end);
+}
+"#;
+
+static TPL_FUNCTION: &str = r#"
+static_assert(rxx::function_layout_ok<{{{c_tp}}}>(), "unsupported layout of {{{c_tp}}}");
+
+extern "C" void {{name}}_delete({{{c_tp}}} &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void {{name}}_clone(const {{{c_tp}}} &self, {{{c_tp}}} *out) noexcept {
+    new (out) {{{c_tp}}}(self);
+}
+
+extern "C" void {{name}}_new_empty({{{c_tp}}} *out) noexcept {
+    new (out) {{{c_tp}}}();
+}
+
+extern "C" bool {{name}}_is_empty(const {{{c_tp}}} &self) noexcept {
+    return !self;
+}
+
+extern "C" void {{name}}_from_rust(void *ctx, void (*drop)(void *), rxx::rust_function<{{{sig_types}}}>::call_t call, {{{c_tp}}} *out) noexcept {
+    new (out) {{{c_tp}}}(rxx::rust_function<{{{sig_types}}}>{std::shared_ptr<void>(ctx, drop), call});
+}
+
+{{#if ret_type}}
+extern "C" void {{name}}_call({{{decl_link_args}}}, {{{ret_type}}} *__ret) noexcept {
+    new (__ret) {{{ret_type}}}(self({{{call_args}}}));
+}
+{{else}}
+extern "C" void {{name}}_call({{{decl_link_args}}}) noexcept {
+    self({{{call_args}}});
+}
+{{/if}}
+"#;
+
 lazy_static! {
     static ref HANDLEBARS: Handlebars<'static> = {
         let mut hb = Handlebars::new();
@@ -142,8 +620,28 @@ lazy_static! {
             ("tpl_void_memfn_mut", TPL_VOID_MEMFN_MUT),
             ("tpl_unique_ptr", TPL_UNIQUE_PTR),
             ("tpl_shared_ptr", TPL_SHARED_PTR),
+            ("tpl_make_shared", TPL_MAKE_SHARED),
             ("tpl_weak_ptr", TPL_WEAK_PTR),
             ("tpl_vector", TPL_VECTOR),
+            ("tpl_atomic_shared_ptr", TPL_ATOMIC_SHARED_PTR),
+            ("tpl_pointer_cast", TPL_POINTER_CAST),
+            ("tpl_shared_from_this", TPL_SHARED_FROM_THIS),
+            ("tpl_type_info", TPL_TYPE_INFO),
+            ("tpl_any", TPL_ANY),
+            ("tpl_optional", TPL_OPTIONAL),
+            ("tpl_variant", TPL_VARIANT),
+            ("tpl_pair", TPL_PAIR),
+            ("tpl_tuple", TPL_TUPLE),
+            ("tpl_array", TPL_ARRAY),
+            ("tpl_complex", TPL_COMPLEX),
+            ("tpl_duration", TPL_DURATION),
+            ("tpl_time_point", TPL_TIME_POINT),
+            ("tpl_unordered_map", TPL_UNORDERED_MAP),
+            ("tpl_map", TPL_MAP),
+            ("tpl_set", TPL_SET),
+            ("tpl_deque", TPL_DEQUE),
+            ("tpl_list", TPL_LIST),
+            ("tpl_function", TPL_FUNCTION),
         ] {
             hb.register_template_string(k, v.trim_start()).unwrap();
         }
@@ -181,7 +679,31 @@ pub struct FnSig<'a> {
     pub c_fn: &'a str,
 
     pub ret_type: ReturnType<'a>,
+    /// A trailing `std::error_code &` argument becomes the error of a Rust
+    /// `Result<T, CxxErrorCode>`, a `std::system_error` thrown by the
+    /// function is caught into it and `T` is value initialized.
     pub args: &'a [(&'a str, &'a str)],
+    /// Arguments passed from Rust as `CxxCallback`, by name with their C++
+    /// signature, e.g. `("visit", "void(int64_t)")`.
+    pub callbacks: &'a [(&'a str, &'a str)],
+    /// Function pointer and `void *` context argument pairs passed from Rust
+    /// as one `&CxxCallbackHandle`, by name with the signature without the
+    /// leading context, e.g. `("cb", "ctx", "void(int)")`. The function
+    /// pointer type can be spelled `rxx::c_callback<void(int)>::fn_t`.
+    pub c_callbacks: &'a [(&'a str, &'a str, &'a str)],
+    /// Catches a `std::system_error` thrown by a function without a trailing
+    /// `std::error_code &`, the shim takes one for it so the Rust side is a
+    /// `Result<T, CxxErrorCode>` too. Other exceptions still terminate, the
+    /// shims are `noexcept`.
+    pub catch_system_error: bool,
+}
+
+// name of a trailing `std::error_code &` argument, a `std::system_error`
+// thrown by the function is stored there
+fn error_code_arg<'a>(args: &[(&str, &'a str)]) -> Option<&'a str> {
+    let (tp, val) = args.last()?;
+    let tp: String = tp.split_whitespace().collect();
+    (tp == "std::error_code&").then_some(*val)
 }
 
 pub fn genc_fn(link_name: &str, fn_sig: FnSig) -> String {
@@ -195,11 +717,42 @@ pub fn genc_fn(link_name: &str, fn_sig: FnSig) -> String {
     let s_call_args = fn_sig
         .args
         .iter()
-        .map(|(_, val)| val.to_string())
+        .map(|(_, val)| {
+            if let Some((cb, _, _)) = fn_sig.c_callbacks.iter().find(|(cb, _, _)| cb == val) {
+                format!("{cb}.call")
+            } else if let Some((cb, _, _)) =
+                fn_sig.c_callbacks.iter().find(|(_, ctx, _)| ctx == val)
+            {
+                format!("{cb}.ctx")
+            } else {
+                val.to_string()
+            }
+        })
         .collect::<Vec<_>>()
         .join(", ");
 
-    let mut s_decl_link_args = s_decl_args.clone();
+    // the shim adds the error code unless the function takes one
+    let add_ec = fn_sig.catch_system_error && error_code_arg(fn_sig.args).is_none();
+    let ec_arg = error_code_arg(fn_sig.args).or(add_ec.then_some("__ec"));
+
+    // a C style callback and its context are passed as one argument
+    let mut s_decl_link_args = fn_sig
+        .args
+        .iter()
+        .filter(|(_, val)| !fn_sig.c_callbacks.iter().any(|(_, ctx, _)| ctx == val))
+        .map(|(tp, val)| {
+            if let Some((_, sig)) = fn_sig.callbacks.iter().find(|(name, _)| name == val) {
+                format!("rxx::callback<{sig}> {val}")
+            } else if let Some((_, _, sig)) = fn_sig.c_callbacks.iter().find(|(cb, _, _)| cb == val)
+            {
+                format!("rxx::c_callback<{sig}> const &{val}")
+            } else {
+                format!("{tp} {val}")
+            }
+        })
+        .chain(add_ec.then(|| "std::error_code &__ec".to_string()))
+        .collect::<Vec<_>>()
+        .join(", ");
 
     if !s_decl_link_args.is_empty() {
         if fn_sig.ret_type.is_object() {
@@ -214,6 +767,11 @@ pub fn genc_fn(link_name: &str, fn_sig: FnSig) -> String {
         }
     }
 
+    let call = |func: &str, call_args: &str| match ec_arg {
+        Some(ec) => format!("rxx::catch_system_error({ec}, [&] {{ return {func}({call_args}); }})"),
+        None => format!("{func}({call_args})"),
+    };
+
     match fn_sig.cls {
         None => {
             let (ret_type, tpl_name) = match fn_sig.ret_type {
@@ -231,8 +789,7 @@ pub fn genc_fn(link_name: &str, fn_sig: FnSig) -> String {
                     "ret_type": ret_type,
                     "decl_link_args": s_decl_link_args,
                     "decl_args": s_decl_args,
-                    "call_args": s_call_args,
-
+                    "call": call("__func", &s_call_args),
                     }),
                 )
                 .unwrap()
@@ -264,8 +821,7 @@ pub fn genc_fn(link_name: &str, fn_sig: FnSig) -> String {
                     "ret_type": ret_type,
                     "decl_link_args": s_decl_link_args,
                     "decl_args": s_decl_args,
-                    "call_args": s_call_args,
-
+                    "call": call("(self.*__func)", &s_call_args),
                     }),
                 )
                 .unwrap()
@@ -297,10 +853,25 @@ pub fn genc_shared_ptr(link_name: &str, c_tp: &str) -> String {
         .unwrap()
 }
 
-pub fn genc_weak_ptr(link_name: &str, c_tp: &str, c_shared_tp: &str) -> String {
+/// `std::make_shared` shims for `SharedPtr::new` and `new_default`, needs a
+/// complete, move and default constructible pointee type, see
+/// `genrs_make_shared!`
+pub fn genc_make_shared(link_name: &str, c_tp: &str) -> String {
     HANDLEBARS
         .render(
-            "tpl_weak_ptr",
+            "tpl_make_shared",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_weak_ptr(link_name: &str, c_tp: &str, c_shared_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_weak_ptr",
             &json!({
             "name": link_name,
             "c_tp": c_tp,
@@ -323,6 +894,338 @@ pub fn genc_vector(link_name: &str, c_tp: &str, c_item_tp: &str) -> String {
         .unwrap()
 }
 
+pub fn genc_unordered_map(link_name: &str, c_tp: &str, c_key_tp: &str, c_value_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_unordered_map",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "c_key_tp": c_key_tp,
+            "c_value_tp": c_value_tp,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_map(link_name: &str, c_tp: &str, c_key_tp: &str, c_value_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_map",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "c_key_tp": c_key_tp,
+            "c_value_tp": c_value_tp,
+            "multi": false,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_multimap(link_name: &str, c_tp: &str, c_key_tp: &str, c_value_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_map",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "c_key_tp": c_key_tp,
+            "c_value_tp": c_value_tp,
+            "multi": true,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_set(link_name: &str, c_tp: &str, c_item_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_set",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "c_item_tp": c_item_tp,
+            "unordered": false,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_unordered_set(link_name: &str, c_tp: &str, c_item_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_set",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "c_item_tp": c_item_tp,
+            "unordered": true,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_deque(link_name: &str, c_tp: &str, c_item_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_deque",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "c_item_tp": c_item_tp,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_list(link_name: &str, c_tp: &str, c_item_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_list",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "c_item_tp": c_item_tp,
+            }),
+        )
+        .unwrap()
+}
+
+/// `std::function` type with the signature of `fn_sig`, `c_fn` is unused.
+pub fn function_type(fn_sig: &FnSig) -> String {
+    let ret_type = match fn_sig.ret_type {
+        ReturnType::None => "void",
+        ReturnType::Object(rt) | ReturnType::Atomic(rt) => rt,
+    };
+    let arg_types = fn_sig
+        .args
+        .iter()
+        .map(|(tp, _)| *tp)
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("std::function<{ret_type}({arg_types})>")
+}
+
+pub fn genc_function(link_name: &str, fn_sig: FnSig) -> String {
+    let c_tp = function_type(&fn_sig);
+    let ret_type = match fn_sig.ret_type {
+        ReturnType::None => "",
+        ReturnType::Object(rt) | ReturnType::Atomic(rt) => rt,
+    };
+    let sig_types = std::iter::once(if ret_type.is_empty() {
+        "void"
+    } else {
+        ret_type
+    })
+    .chain(fn_sig.args.iter().map(|(tp, _)| *tp))
+    .collect::<Vec<_>>()
+    .join(", ");
+    let decl_link_args = std::iter::once(format!("const {c_tp} &self"))
+        .chain(fn_sig.args.iter().map(|(tp, val)| format!("{tp} {val}")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let call_args = fn_sig
+        .args
+        .iter()
+        .map(|(_, val)| val.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    HANDLEBARS
+        .render(
+            "tpl_function",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "ret_type": ret_type,
+            "sig_types": sig_types,
+            "decl_link_args": decl_link_args,
+            "call_args": call_args,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_atomic_shared_ptr(link_name: &str, c_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_atomic_shared_ptr",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_pointer_cast(link_name: &str, c_base_tp: &str, c_derived_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_pointer_cast",
+            &json!({
+            "name": link_name,
+            "c_base_tp": c_base_tp,
+            "c_derived_tp": c_derived_tp,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_shared_from_this(link_name: &str, c_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_shared_from_this",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            }),
+        )
+        .unwrap()
+}
+
+pub fn genc_type_info(link_name: &str, c_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_type_info",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            }),
+        )
+        .unwrap()
+}
+
+/// requires C++17, also provides the `genc_type_info` glue
+pub fn genc_any(link_name: &str, c_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_any",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            }),
+        )
+        .unwrap()
+}
+
+/// layout check for returning `std::pair` by value as `CxxPair`
+pub fn genc_pair(c_first_tp: &str, c_second_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_pair",
+            &json!({
+            "c_first_tp": c_first_tp,
+            "c_second_tp": c_second_tp,
+            }),
+        )
+        .unwrap()
+}
+
+/// layout check for using `std::array<c_item_tp, n>` as `CxxArray<T, N>`
+pub fn genc_array(c_item_tp: &str, n: usize) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_array",
+            &json!({
+            "c_item_tp": c_item_tp,
+            "n": n,
+            }),
+        )
+        .unwrap()
+}
+
+/// layout check for using `std::complex<c_item_tp>` as `CxxComplex<T>`, passed
+/// by value and returned with `ReturnType::Atomic`
+pub fn genc_complex(c_item_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_complex",
+            &json!({
+            "c_item_tp": c_item_tp,
+            }),
+        )
+        .unwrap()
+}
+
+/// layout check for using the `std::chrono::duration` type `c_tp` as
+/// `CxxDuration<num, den>`
+pub fn genc_duration(c_tp: &str, num: i64, den: i64) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_duration",
+            &json!({
+            "c_tp": c_tp,
+            "num": num,
+            "den": den,
+            }),
+        )
+        .unwrap()
+}
+
+/// layout check for a `std::chrono::time_point` type `c_tp` counted in
+/// `CxxDuration<num, den>`, e.g. `CxxSystemTime` is
+/// `genc_time_point("std::chrono::system_clock::time_point", 1, 1_000_000_000)`
+pub fn genc_time_point(c_tp: &str, num: i64, den: i64) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_time_point",
+            &json!({
+            "c_tp": c_tp,
+            "num": num,
+            "den": den,
+            }),
+        )
+        .unwrap()
+}
+
+/// `c_tps` are the element types of `std::tuple` in order
+pub fn genc_tuple(link_name: &str, c_tps: &[&str]) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_tuple",
+            &json!({
+            "name": link_name,
+            "c_tp": format!("std::tuple<{}>", c_tps.join(", ")),
+            "c_tps": c_tps,
+            }),
+        )
+        .unwrap()
+}
+
+/// requires C++17, `c_tp` is the element type
+pub fn genc_optional(link_name: &str, c_tp: &str) -> String {
+    HANDLEBARS
+        .render(
+            "tpl_optional",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            }),
+        )
+        .unwrap()
+}
+
+/// requires C++17, `alts` are the `(name, type)` of the alternatives in order,
+/// names match the variants of the `genrs_variant!` enum
+pub fn genc_variant(link_name: &str, c_tp: &str, alts: &[(&str, &str)]) -> String {
+    let alts = alts
+        .iter()
+        .map(|(name, tp)| json!({"name": name, "tp": tp}))
+        .collect::<Vec<_>>();
+
+    HANDLEBARS
+        .render(
+            "tpl_variant",
+            &json!({
+            "name": link_name,
+            "c_tp": c_tp,
+            "alts": alts,
+            }),
+        )
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,6 +1274,112 @@ extern "C" void rxx_Matrix3d_print(Matrix3d const & self) noexcept {
         );
     }
 
+    #[test]
+    fn test_fn_callback() {
+        let s = genc_fn(
+            "rxx_Tree_for_each",
+            FnSig {
+                c_fn: "Tree_for_each",
+                args: &[
+                    ("Tree const &", "self"),
+                    ("std::function<void(Node const &)> const &", "visit"),
+                ],
+                callbacks: &[("visit", "void(Node const &)")],
+                ..FnSig::default()
+            },
+        );
+
+        assert_eq!(
+            s,
+            r#"
+extern "C" void rxx_Tree_for_each(Tree const & self, rxx::callback<void(Node const &)> visit) noexcept {
+    void (*__func)(Tree const & self, std::function<void(Node const &)> const & visit) = Tree_for_each;
+    __func(self, visit);
+}
+"#
+            .trim_start()
+        );
+    }
+
+    #[test]
+    fn test_fn_c_callback() {
+        let s = genc_fn(
+            "rxx_register",
+            FnSig {
+                c_fn: "register_handler",
+                ret_type: ReturnType::Atomic("int"),
+                args: &[
+                    ("int", "id"),
+                    ("rxx::c_callback<void(int)>::fn_t", "cb"),
+                    ("void *", "ctx"),
+                ],
+                c_callbacks: &[("cb", "ctx", "void(int)")],
+                ..FnSig::default()
+            },
+        );
+
+        assert_eq!(
+            s,
+            r#"
+extern "C" int rxx_register(int id, rxx::c_callback<void(int)> const &cb) noexcept {
+    int (*__func)(int id, rxx::c_callback<void(int)>::fn_t cb, void * ctx) = register_handler;
+    return __func(id, cb.call, cb.ctx);
+}
+"#
+            .trim_start()
+        );
+    }
+
+    #[test]
+    fn test_fn_error_code() {
+        let s = genc_fn(
+            "rxx_Socket_connect",
+            FnSig {
+                cls: Some("Socket"),
+                c_fn: "&$C::connect",
+                is_mut: true,
+                ret_type: ReturnType::Object("std::string"),
+                args: &[("int", "port"), ("std::error_code&", "ec")],
+                ..FnSig::default()
+            },
+        );
+
+        assert_eq!(
+            s,
+            r#"
+extern "C" void rxx_Socket_connect(Socket &self, int port, std::error_code& ec, std::string *__ret) noexcept {
+    std::string (Socket::*__func)(int port, std::error_code& ec) = &Socket::connect;
+    new (__ret) std::string(rxx::catch_system_error(ec, [&] { return (self.*__func)(port, ec); }));
+}
+"#
+            .trim_start()
+        );
+
+        let s = genc_fn(
+            "rxx_Socket_send",
+            FnSig {
+                cls: Some("Socket"),
+                c_fn: "&$C::send",
+                is_mut: true,
+                ret_type: ReturnType::Atomic("size_t"),
+                args: &[("int", "n")],
+                catch_system_error: true,
+                ..FnSig::default()
+            },
+        );
+
+        assert_eq!(
+            s,
+            r#"
+extern "C" size_t rxx_Socket_send(Socket &self, int n, std::error_code &__ec) noexcept {
+    size_t (Socket::*__func)(int n) = &Socket::send;
+    return rxx::catch_system_error(__ec, [&] { return (self.*__func)(n); });
+}
+"#
+            .trim_start()
+        );
+    }
+
     #[test]
     fn test_std() {
         let s = genc_unique_ptr("rxx_unique_string", "std::unique_ptr<std::string>");
@@ -395,6 +1404,24 @@ extern "C" void rxx_shared_string_clone(const std::shared_ptr<std::string> &self
 }
 "#.trim_start());
 
+        let s = genc_make_shared("rxx_shared_string", "std::shared_ptr<std::string>");
+        assert_eq!(
+            s,
+            r#"
+static_assert(std::is_move_constructible<std::shared_ptr<std::string>::element_type>::value, "std::shared_ptr<std::string> element is not move constructible");
+static_assert(std::is_default_constructible<std::shared_ptr<std::string>::element_type>::value, "std::shared_ptr<std::string> element is not default constructible");
+
+extern "C" void rxx_shared_string_new(void *value, std::shared_ptr<std::string> *out) noexcept {
+    rxx::shared_ptr_new(value, out);
+}
+
+extern "C" void rxx_shared_string_new_default(std::shared_ptr<std::string> *out) noexcept {
+    rxx::shared_ptr_new_default(out);
+}
+"#
+            .trim_start()
+        );
+
         let s = genc_weak_ptr(
             "rxx_weak_string",
             "std::weak_ptr<std::string>",
@@ -449,4 +1476,212 @@ extern "C" void rxx_vector_string_pop_back(std::vector<std::string> &self, std::
 }
 "#.trim_start());
     }
+
+    #[test]
+    fn test_pointer_cast() {
+        let s = genc_pointer_cast("rxx_Derived_Base", "Base", "Derived");
+        assert_eq!(s, r#"
+extern "C" void rxx_Derived_Base_shared_downcast(const std::shared_ptr<Base> &self, std::shared_ptr<Derived> *out) noexcept {
+    rxx::shared_ptr_dynamic_cast(self, out);
+}
+
+extern "C" void rxx_Derived_Base_shared_static_downcast(const std::shared_ptr<Base> &self, std::shared_ptr<Derived> *out) noexcept {
+    rxx::shared_ptr_static_cast(self, out);
+}
+
+extern "C" void rxx_Derived_Base_shared_upcast(const std::shared_ptr<Derived> &self, std::shared_ptr<Base> *out) noexcept {
+    rxx::shared_ptr_static_cast(self, out);
+}
+
+extern "C" void rxx_Derived_Base_unique_downcast(std::unique_ptr<Base> &self, std::unique_ptr<Derived> *out) noexcept {
+    rxx::unique_ptr_dynamic_cast(self, out);
+}
+
+extern "C" void rxx_Derived_Base_unique_static_downcast(std::unique_ptr<Base> &self, std::unique_ptr<Derived> *out) noexcept {
+    rxx::unique_ptr_static_cast(self, out);
+}
+
+extern "C" void rxx_Derived_Base_unique_upcast(std::unique_ptr<Derived> &self, std::unique_ptr<Base> *out) noexcept {
+    rxx::unique_ptr_static_cast(self, out);
+}
+"#.trim_start());
+    }
+
+    #[test]
+    fn test_tuple() {
+        let s = genc_pair("int", "std::string");
+        assert_eq!(s, r#"
+static_assert(rxx::pair_layout_ok<int, std::string>(), "unsupported layout of std::pair<int, std::string>");
+"#.trim_start());
+
+        let s = genc_array("double", 3);
+        assert_eq!(
+            s,
+            r#"
+static_assert(rxx::array_layout_ok<double, 3>(), "unsupported layout of std::array<double, 3>");
+"#
+            .trim_start()
+        );
+
+        let s = genc_complex("double");
+        assert_eq!(
+            s,
+            r#"
+static_assert(rxx::complex_layout_ok<double>(), "unsupported layout of std::complex<double>");
+"#
+            .trim_start()
+        );
+
+        let s = genc_duration("std::chrono::milliseconds", 1, 1000);
+        assert_eq!(
+            s,
+            r#"
+static_assert(rxx::duration_layout_ok<std::chrono::milliseconds>(1, 1000), "std::chrono::milliseconds is not CxxDuration<1, 1000>");
+"#
+            .trim_start()
+        );
+
+        let s = genc_time_point("std::chrono::steady_clock::time_point", 1, 1000000000);
+        assert_eq!(
+            s,
+            r#"
+static_assert(rxx::duration_layout_ok<std::chrono::steady_clock::time_point::duration>(1, 1000000000), "std::chrono::steady_clock::time_point is not counted in CxxDuration<1, 1000000000>");
+"#
+            .trim_start()
+        );
+
+        let s = genc_tuple("rxx_tuple_i64_f64", &["int64_t", "double"]);
+        assert_eq!(s, r#"
+static_assert(rxx::tuple_layout_ok<std::tuple<int64_t, double>, int64_t, double>(), "unsupported layout of std::tuple<int64_t, double>");
+
+extern "C" void rxx_tuple_i64_f64_delete(std::tuple<int64_t, double> &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void rxx_tuple_i64_f64_new(int64_t &v0, double &v1, std::tuple<int64_t, double> *out) noexcept {
+    rxx::tuple_new(out, v0, v1);
+}
+
+extern "C" void rxx_tuple_i64_f64_take(std::tuple<int64_t, double> &self, int64_t *out0, double *out1) noexcept {
+    rxx::tuple_take(self, out0, out1);
+}
+
+extern "C" const int64_t &rxx_tuple_i64_f64_get_0(const std::tuple<int64_t, double> &self) noexcept {
+    return std::get<0>(self);
+}
+
+extern "C" int64_t *rxx_tuple_i64_f64_get_mut_0(std::tuple<int64_t, double> &self) noexcept {
+    return &std::get<0>(self);
+}
+
+extern "C" const double &rxx_tuple_i64_f64_get_1(const std::tuple<int64_t, double> &self) noexcept {
+    return std::get<1>(self);
+}
+
+extern "C" double *rxx_tuple_i64_f64_get_mut_1(std::tuple<int64_t, double> &self) noexcept {
+    return &std::get<1>(self);
+}
+"#.trim_start());
+    }
+
+    #[test]
+    fn test_variant() {
+        let s = genc_variant(
+            "rxx_Msg",
+            "Msg",
+            &[("Int", "int64_t"), ("Text", "std::string")],
+        );
+        assert_eq!(
+            s,
+            r#"
+static_assert(rxx::variant_layout_ok<Msg, int64_t, std::string>(), "unsupported layout of Msg");
+
+extern "C" void rxx_Msg_delete(Msg &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" size_t rxx_Msg_index(const Msg &self) noexcept {
+    return rxx::variant_index(self);
+}
+
+extern "C" const int64_t *rxx_Msg_get_if_Int(const Msg &self) noexcept {
+    return std::get_if<0>(&self);
+}
+
+extern "C" void rxx_Msg_new_Int(int64_t &value, Msg *out) noexcept {
+    rxx::variant_new<0>(value, out);
+}
+
+extern "C" void rxx_Msg_emplace_Int(Msg &self, int64_t &value) noexcept {
+    rxx::variant_emplace<0>(self, value);
+}
+
+extern "C" const std::string *rxx_Msg_get_if_Text(const Msg &self) noexcept {
+    return std::get_if<1>(&self);
+}
+
+extern "C" void rxx_Msg_new_Text(std::string &value, Msg *out) noexcept {
+    rxx::variant_new<1>(value, out);
+}
+
+extern "C" void rxx_Msg_emplace_Text(Msg &self, std::string &value) noexcept {
+    rxx::variant_emplace<1>(self, value);
+}
+"#
+            .trim_start()
+        );
+    }
+
+    #[test]
+    fn test_function() {
+        let s = genc_function(
+            "rxx_function_i64_f64",
+            FnSig {
+                ret_type: ReturnType::Atomic("double"),
+                args: &[("int64_t", "x"), ("double", "y")],
+                ..FnSig::default()
+            },
+        );
+
+        assert_eq!(
+            s,
+            r#"
+static_assert(rxx::function_layout_ok<std::function<double(int64_t, double)>>(), "unsupported layout of std::function<double(int64_t, double)>");
+
+extern "C" void rxx_function_i64_f64_delete(std::function<double(int64_t, double)> &self) noexcept {
+    rxx::destroy(&self);
+}
+
+extern "C" void rxx_function_i64_f64_clone(const std::function<double(int64_t, double)> &self, std::function<double(int64_t, double)> *out) noexcept {
+    new (out) std::function<double(int64_t, double)>(self);
+}
+
+extern "C" void rxx_function_i64_f64_new_empty(std::function<double(int64_t, double)> *out) noexcept {
+    new (out) std::function<double(int64_t, double)>();
+}
+
+extern "C" bool rxx_function_i64_f64_is_empty(const std::function<double(int64_t, double)> &self) noexcept {
+    return !self;
+}
+
+extern "C" void rxx_function_i64_f64_from_rust(void *ctx, void (*drop)(void *), rxx::rust_function<double, int64_t, double>::call_t call, std::function<double(int64_t, double)> *out) noexcept {
+    new (out) std::function<double(int64_t, double)>(rxx::rust_function<double, int64_t, double>{std::shared_ptr<void>(ctx, drop), call});
+}
+
+extern "C" void rxx_function_i64_f64_call(const std::function<double(int64_t, double)> &self, int64_t x, double y, double *__ret) noexcept {
+    new (__ret) double(self(x, y));
+}
+"#
+            .trim_start()
+        );
+
+        let s = genc_function("rxx_function_void", FnSig::default());
+        assert!(s.contains(
+            r#"
+extern "C" void rxx_function_void_call(const std::function<void()> &self) noexcept {
+    self();
+}
+"#
+        ));
+    }
 }
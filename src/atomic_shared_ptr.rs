@@ -0,0 +1,116 @@
+use crate::shared_ptr::{SharedPtr, SharedPtrTarget};
+use core::cell::UnsafeCell;
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+pub trait AtomicSharedPtrTarget: SharedPtrTarget {
+    unsafe fn __load(this: *const c_void, out: *mut c_void);
+    unsafe fn __store(this: *mut c_void, value: *mut c_void);
+    unsafe fn __exchange(this: *mut c_void, value: *mut c_void, out: *mut c_void);
+    unsafe fn __compare_exchange(
+        this: *mut c_void,
+        expected: *mut c_void,
+        desired: *const c_void,
+    ) -> bool;
+}
+
+/// A `std::shared_ptr<T>` only accessed through the C++ `std::atomic_*` free
+/// functions, so it can be shared with C++ code doing the same.
+#[repr(transparent)]
+pub struct AtomicSharedPtr<T: AtomicSharedPtrTarget> {
+    inner: UnsafeCell<SharedPtr<T>>,
+}
+
+impl<T: AtomicSharedPtrTarget> AtomicSharedPtr<T> {
+    pub fn new(value: SharedPtr<T>) -> Self {
+        AtomicSharedPtr {
+            inner: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn null() -> Self {
+        Self::new(SharedPtr::null())
+    }
+
+    pub fn into_inner(self) -> SharedPtr<T> {
+        self.inner.into_inner()
+    }
+
+    pub fn load(&self) -> SharedPtr<T> {
+        let mut out = MaybeUninit::<SharedPtr<T>>::uninit();
+        unsafe {
+            T::__load(self.inner.get() as *const c_void, out.as_mut_ptr().cast());
+            out.assume_init()
+        }
+    }
+
+    pub fn store(&self, value: SharedPtr<T>) {
+        // C++ moves out of `value` and destroys it
+        let mut value = ManuallyDrop::new(value);
+        unsafe {
+            T::__store(
+                self.inner.get() as *mut c_void,
+                &mut *value as *mut SharedPtr<T> as *mut c_void,
+            );
+        }
+    }
+
+    pub fn swap(&self, value: SharedPtr<T>) -> SharedPtr<T> {
+        let mut value = ManuallyDrop::new(value);
+        let mut out = MaybeUninit::<SharedPtr<T>>::uninit();
+        unsafe {
+            T::__exchange(
+                self.inner.get() as *mut c_void,
+                &mut *value as *mut SharedPtr<T> as *mut c_void,
+                out.as_mut_ptr().cast(),
+            );
+            out.assume_init()
+        }
+    }
+
+    /// Stores `new` if the current value shares ownership with and points to
+    /// the same object as `current`. Returns the previous value on success
+    /// and the current value on failure, `new` is dropped on failure.
+    pub fn compare_exchange(
+        &self,
+        current: &SharedPtr<T>,
+        new: SharedPtr<T>,
+    ) -> Result<SharedPtr<T>, SharedPtr<T>> {
+        let mut expected = current.clone();
+        let ok = unsafe {
+            T::__compare_exchange(
+                self.inner.get() as *mut c_void,
+                &mut expected as *mut SharedPtr<T> as *mut c_void,
+                &new as *const SharedPtr<T> as *const c_void,
+            )
+        };
+
+        if ok {
+            Ok(expected)
+        } else {
+            Err(expected)
+        }
+    }
+}
+
+unsafe impl<T> Send for AtomicSharedPtr<T> where T: Send + Sync + AtomicSharedPtrTarget {}
+unsafe impl<T> Sync for AtomicSharedPtr<T> where T: Send + Sync + AtomicSharedPtrTarget {}
+
+impl<T: AtomicSharedPtrTarget> Default for AtomicSharedPtr<T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T: AtomicSharedPtrTarget> From<SharedPtr<T>> for AtomicSharedPtr<T> {
+    fn from(value: SharedPtr<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Debug + AtomicSharedPtrTarget> Debug for AtomicSharedPtr<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.load(), formatter)
+    }
+}
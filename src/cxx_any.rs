@@ -0,0 +1,211 @@
+use crate::shared_ptr::{SharedPtr, SharedPtrTarget};
+use core::ffi::{c_char, c_void, CStr};
+use core::fmt::{self, Debug};
+use core::marker::{PhantomData, PhantomPinned};
+
+#[cfg(feature = "cxx17")]
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+pub trait TypeInfoTarget {
+    unsafe fn __type_info() -> &'static CxxTypeInfo;
+}
+
+/// Binding to C++ `std::type_info`.
+#[repr(C)]
+pub struct CxxTypeInfo {
+    _private: [u8; 0],
+    _pin: PhantomData<PhantomPinned>,
+}
+
+impl CxxTypeInfo {
+    pub fn of<T: TypeInfoTarget>() -> &'static CxxTypeInfo {
+        unsafe { T::__type_info() }
+    }
+
+    /// Implementation defined name, mangled on gcc and clang.
+    pub fn name(&self) -> &str {
+        extern "C" {
+            fn rxx_type_info_name(this: &CxxTypeInfo) -> *const c_char;
+        }
+        unsafe { CStr::from_ptr(rxx_type_info_name(self)).to_str().unwrap() }
+    }
+}
+
+impl PartialEq for CxxTypeInfo {
+    fn eq(&self, other: &Self) -> bool {
+        extern "C" {
+            fn rxx_type_info_eq(this: &CxxTypeInfo, other: &CxxTypeInfo) -> bool;
+        }
+        unsafe { rxx_type_info_eq(self, other) }
+    }
+}
+
+impl Eq for CxxTypeInfo {}
+
+impl Debug for CxxTypeInfo {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+/// Pointee of C++ `std::shared_ptr<void>`.
+#[repr(C)]
+pub struct CxxVoid {
+    _private: [u8; 0],
+}
+
+impl SharedPtrTarget for CxxVoid {
+    unsafe fn __drop(this: *mut c_void) {
+        extern "C" {
+            fn rxx_shared_ptr_void_delete(this: *mut c_void);
+        }
+        rxx_shared_ptr_void_delete(this);
+    }
+
+    unsafe fn __clone(this: *const c_void, out: *mut c_void) {
+        extern "C" {
+            fn rxx_shared_ptr_void_clone(this: *const c_void, out: *mut c_void);
+        }
+        rxx_shared_ptr_void_clone(this, out);
+    }
+}
+
+impl SharedPtr<CxxVoid> {
+    /// Checked downcast, `tag` is the `typeid` of the pointee handed out by C++
+    /// along with the pointer, returns `None` if it is not `T`.
+    pub fn downcast_tagged<T>(&self, tag: &CxxTypeInfo) -> Option<SharedPtr<T>>
+    where
+        T: SharedPtrTarget + TypeInfoTarget,
+    {
+        if *tag == *CxxTypeInfo::of::<T>() {
+            Some(unsafe { self.alias(self.get_ptr() as *const T) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: SharedPtrTarget> SharedPtr<T> {
+    /// Type erased copy, `T` must be thread safe since `SharedPtr<CxxVoid>`
+    /// is `Send` and `Sync`.
+    pub fn to_void(&self) -> SharedPtr<CxxVoid>
+    where
+        T: Send + Sync,
+    {
+        unsafe { self.alias(self.get_ptr() as *const CxxVoid) }
+    }
+}
+
+#[cfg(feature = "cxx17")]
+pub trait AnyElement: TypeInfoTarget + Sized {
+    unsafe fn __any_new(value: &mut Self, out: *mut CxxAny);
+    unsafe fn __any_cast(this: &CxxAny) -> *const Self;
+    unsafe fn __any_cast_mut(this: &mut CxxAny) -> *mut Self;
+}
+
+/// Binding to C++ `std::any`, needs C++17.
+#[cfg(feature = "cxx17")]
+#[repr(C)]
+pub struct CxxAny {
+    // manager function and storage, checked in wrapper.cc
+    _space: MaybeUninit<[usize; 2]>,
+}
+
+#[cfg(feature = "cxx17")]
+impl CxxAny {
+    pub fn new<T: AnyElement>(value: T) -> Self {
+        // C++ calls move constructor followed by destructor on `value`
+        let mut value = ManuallyDrop::new(value);
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            T::__any_new(&mut value, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn has_value(&self) -> bool {
+        extern "C" {
+            fn rxx_any_has_value(this: &CxxAny) -> bool;
+        }
+        unsafe { rxx_any_has_value(self) }
+    }
+
+    /// `typeid(void)` if empty.
+    pub fn type_info(&self) -> &'static CxxTypeInfo {
+        extern "C" {
+            fn rxx_any_type(this: &CxxAny) -> &'static CxxTypeInfo;
+        }
+        unsafe { rxx_any_type(self) }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_info().name()
+    }
+
+    pub fn is<T: TypeInfoTarget>(&self) -> bool {
+        *self.type_info() == *CxxTypeInfo::of::<T>()
+    }
+
+    pub fn downcast_ref<T: AnyElement>(&self) -> Option<&T> {
+        unsafe { T::__any_cast(self).as_ref() }
+    }
+
+    pub fn downcast_mut<T: AnyElement>(&mut self) -> Option<&mut T> {
+        unsafe { T::__any_cast_mut(self).as_mut() }
+    }
+
+    pub fn reset(&mut self) {
+        extern "C" {
+            fn rxx_any_reset(this: &mut CxxAny);
+        }
+        unsafe { rxx_any_reset(self) }
+    }
+}
+
+#[cfg(feature = "cxx17")]
+impl Default for CxxAny {
+    fn default() -> Self {
+        extern "C" {
+            fn rxx_any_new(out: *mut CxxAny);
+        }
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            rxx_any_new(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+}
+
+#[cfg(feature = "cxx17")]
+impl Clone for CxxAny {
+    fn clone(&self) -> Self {
+        extern "C" {
+            fn rxx_any_clone(this: &CxxAny, out: *mut CxxAny);
+        }
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            rxx_any_clone(self, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+}
+
+#[cfg(feature = "cxx17")]
+impl Drop for CxxAny {
+    fn drop(&mut self) {
+        extern "C" {
+            fn rxx_any_destroy(this: &mut CxxAny);
+        }
+        unsafe { rxx_any_destroy(self) }
+    }
+}
+
+#[cfg(feature = "cxx17")]
+impl Debug for CxxAny {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("CxxAny")
+            .field("type", self.type_info())
+            .finish()
+    }
+}
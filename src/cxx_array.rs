@@ -0,0 +1,61 @@
+use core::fmt::{self, Debug};
+use core::ops::{Deref, DerefMut};
+
+/// Binding to C++ `std::array<T, N>`, passed by value like a C struct holding
+/// `[T; N]`. `genc_array` checks at build time that it has no padding and is
+/// trivially copyable.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CxxArray<T, const N: usize> {
+    inner: [T; N],
+}
+
+impl<T, const N: usize> CxxArray<T, N> {
+    pub const fn new(inner: [T; N]) -> Self {
+        CxxArray { inner }
+    }
+
+    pub fn into_inner(self) -> [T; N] {
+        self.inner
+    }
+}
+
+impl<T, const N: usize> Deref for CxxArray<T, N> {
+    type Target = [T; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T, const N: usize> DerefMut for CxxArray<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for CxxArray<T, N> {
+    fn from(inner: [T; N]) -> Self {
+        CxxArray { inner }
+    }
+}
+
+impl<T, const N: usize> From<CxxArray<T, N>> for [T; N] {
+    fn from(array: CxxArray<T, N>) -> Self {
+        array.inner
+    }
+}
+
+impl<T: Default, const N: usize> Default for CxxArray<T, N> {
+    fn default() -> Self {
+        CxxArray {
+            inner: core::array::from_fn(|_| T::default()),
+        }
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for CxxArray<T, N> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.inner, formatter)
+    }
+}
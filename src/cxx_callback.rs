@@ -0,0 +1,188 @@
+use core::any::Any;
+use core::cell::RefCell;
+use core::ffi::c_void;
+use core::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Callback signature callable by the closure `C`. Implemented for `fn`
+/// pointer types taking up to 6 arguments by value, such as
+/// `fn(i64) -> i64`, with `C: FnMut`. A signature with reference arguments
+/// needs a marker type declared by `genrs_callback!`.
+pub trait CallbackSig<C> {
+    #[doc(hidden)]
+    fn __trampoline() -> *const c_void;
+}
+
+/// Non-escaping callback for C++ visitor APIs, borrows a closure for `'a`.
+///
+/// Lowers to `rxx::callback<R(Args...)>`, a function pointer plus a `void *`
+/// context, which C++ converts to `std::function` or any other callable
+/// parameter. Declare the parameter in `FnSig::callbacks`. C++ must not keep
+/// it past the call.
+///
+/// A panic in the closure is caught and resumed by the `genrs_fn!` wrapper
+/// once C++ returns. Until then C++ gets a value-initialized result, or
+/// `std::terminate` if `R` has no default constructor, and further calls
+/// skip the closure.
+#[repr(C)]
+pub struct CxxCallback<'a, F> {
+    call: *const c_void,
+    ctx: *mut c_void,
+    _pd: PhantomData<(&'a mut (), F)>,
+}
+
+impl<'a, F> CxxCallback<'a, F> {
+    pub fn new<C>(f: &'a mut C) -> Self
+    where
+        F: CallbackSig<C>,
+    {
+        CxxCallback {
+            call: F::__trampoline(),
+            ctx: f as *mut C as *mut c_void,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<'a, F: CallbackSig<C>, C> From<&'a mut C> for CxxCallback<'a, F> {
+    fn from(f: &'a mut C) -> Self {
+        Self::new(f)
+    }
+}
+
+/// Callback signature callable by the closure `C` from any thread, for
+/// `CxxCallbackHandle`, with `C: Fn + Send + Sync`. Implemented like
+/// `CallbackSig`.
+pub trait CCallbackSig<C> {
+    #[doc(hidden)]
+    fn __trampoline() -> *const c_void;
+}
+
+/// C style callback, a function pointer taking `void *ctx` first plus the
+/// context, for C++ APIs that store it and call it later, possibly from
+/// other threads. Declare the parameters in `FnSig::c_callbacks` and pass
+/// the handle by reference.
+///
+/// Dropping the handle frees the closure, see `CxxCallbackHandle::new`.
+#[repr(C)]
+pub struct CxxCallbackHandle<F> {
+    call: *const c_void,
+    ctx: *mut c_void,
+    drop: unsafe extern "C" fn(*mut c_void),
+    _pd: PhantomData<F>,
+}
+
+// the closure is `Send + Sync`
+unsafe impl<F> Send for CxxCallbackHandle<F> {}
+unsafe impl<F> Sync for CxxCallbackHandle<F> {}
+
+impl<F> CxxCallbackHandle<F> {
+    /// # Safety
+    ///
+    /// C++ may call the handle until it is dropped, it must be unregistered
+    /// from every C++ API it was passed to before that.
+    pub unsafe fn new<C>(f: C) -> Self
+    where
+        F: CCallbackSig<C>,
+        C: Send + Sync + 'static,
+    {
+        unsafe extern "C" fn drop_ctx<C>(ctx: *mut c_void) {
+            drop(Box::from_raw(ctx as *mut C));
+        }
+
+        CxxCallbackHandle {
+            call: F::__trampoline(),
+            ctx: Box::into_raw(Box::new(f)) as *mut c_void,
+            drop: drop_ctx::<C>,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<F> Drop for CxxCallbackHandle<F> {
+    fn drop(&mut self) {
+        unsafe { (self.drop)(self.ctx) }
+    }
+}
+
+std::thread_local! {
+    // a panic caught in a `CxxCallback`, C++ frames can't be unwound
+    static CALLBACK_PANIC: RefCell<Option<Box<dyn Any + Send>>> = const { RefCell::new(None) };
+}
+
+/// Runs the closure of a `CxxCallback` trampoline, `false` if it panicked
+/// and `ret` was not written.
+#[doc(hidden)]
+pub unsafe fn __call_callback<R>(ret: *mut R, f: impl FnOnce() -> R) -> bool {
+    if CALLBACK_PANIC.with(|slot| slot.borrow().is_some()) {
+        return false;
+    }
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(val) => {
+            ret.write(val);
+            true
+        }
+        Err(payload) => {
+            CALLBACK_PANIC.with(|slot| *slot.borrow_mut() = Some(payload));
+            false
+        }
+    }
+}
+
+/// Resumes a panic caught by a `CxxCallback` during the last C++ call.
+#[doc(hidden)]
+pub fn __resume_callback_panic() {
+    if let Some(payload) = CALLBACK_PANIC.with(|slot| slot.borrow_mut().take()) {
+        panic::resume_unwind(payload);
+    }
+}
+
+// a panic can't unwind out of the `CCallbackSig` trampolines, it aborts
+macro_rules! callback_fn {
+    ($($arg:ident: $tp:ident),*) => {
+        impl<C, R, $($tp),*> CallbackSig<C> for fn($($tp),*) -> R
+        where
+            C: FnMut($($tp),*) -> R,
+        {
+            fn __trampoline() -> *const c_void {
+                // `ret` points to a dummy byte when `R` is `()`
+                unsafe extern "C" fn trampoline<C, R, $($tp),*>(
+                    ctx: *mut c_void,
+                    $($arg: $tp,)*
+                    ret: *mut R,
+                ) -> bool
+                where
+                    C: FnMut($($tp),*) -> R,
+                {
+                    let f = &mut *(ctx as *mut C);
+                    __call_callback(ret, || f($($arg),*))
+                }
+                trampoline::<C, R, $($tp),*> as *const c_void
+            }
+        }
+
+        impl<C, R, $($tp),*> CCallbackSig<C> for fn($($tp),*) -> R
+        where
+            C: Fn($($tp),*) -> R + Send + Sync + 'static,
+        {
+            fn __trampoline() -> *const c_void {
+                unsafe extern "C" fn trampoline<C, R, $($tp),*>(ctx: *mut c_void, $($arg: $tp),*) -> R
+                where
+                    C: Fn($($tp),*) -> R,
+                {
+                    let f = &*(ctx as *const C);
+                    f($($arg),*)
+                }
+                trampoline::<C, R, $($tp),*> as *const c_void
+            }
+        }
+    };
+}
+
+callback_fn!();
+callback_fn!(a0: A0);
+callback_fn!(a0: A0, a1: A1);
+callback_fn!(a0: A0, a1: A1, a2: A2);
+callback_fn!(a0: A0, a1: A1, a2: A2, a3: A3);
+callback_fn!(a0: A0, a1: A1, a2: A2, a3: A3, a4: A4);
+callback_fn!(a0: A0, a1: A1, a2: A2, a3: A3, a4: A4, a5: A5);
@@ -0,0 +1,184 @@
+use core::fmt::{self, Display};
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// Conversion between C++ and Rust time types failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChronoError {
+    /// `Duration` can't be negative.
+    Negative,
+    /// Out of range of the target type.
+    Overflow,
+}
+
+impl Display for ChronoError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match self {
+            ChronoError::Negative => "negative duration",
+            ChronoError::Overflow => "time value out of range",
+        })
+    }
+}
+
+impl std::error::Error for ChronoError {}
+
+/// Binding to C++ `std::chrono::duration<int64_t, std::ratio<NUM, DEN>>`,
+/// passed by value. `genc_duration` checks the representation and period at
+/// build time.
+#[repr(transparent)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CxxDuration<const NUM: i64, const DEN: i64> {
+    count: i64,
+}
+
+pub type CxxNanoseconds = CxxDuration<1, 1_000_000_000>;
+pub type CxxMicroseconds = CxxDuration<1, 1_000_000>;
+pub type CxxMilliseconds = CxxDuration<1, 1_000>;
+pub type CxxSeconds = CxxDuration<1, 1>;
+pub type CxxMinutes = CxxDuration<60, 1>;
+pub type CxxHours = CxxDuration<3600, 1>;
+
+impl<const NUM: i64, const DEN: i64> CxxDuration<NUM, DEN> {
+    pub const fn from_count(count: i64) -> Self {
+        CxxDuration { count }
+    }
+
+    /// Number of ticks, like `count()`.
+    pub const fn count(&self) -> i64 {
+        self.count
+    }
+
+    // a large `NUM` or `DEN` can overflow even `i128`
+    fn as_nanos(&self) -> Result<i128, ChronoError> {
+        (self.count as i128)
+            .checked_mul(NUM as i128)
+            .and_then(|n| n.checked_mul(NANOS_PER_SEC))
+            .and_then(|n| n.checked_div(DEN as i128))
+            .ok_or(ChronoError::Overflow)
+    }
+
+    fn from_nanos(nanos: i128) -> Result<Self, ChronoError> {
+        nanos
+            .checked_mul(DEN as i128)
+            .and_then(|n| n.checked_div(NUM as i128 * NANOS_PER_SEC))
+            .and_then(|count| i64::try_from(count).ok())
+            .map(Self::from_count)
+            .ok_or(ChronoError::Overflow)
+    }
+}
+
+/// Fails for negative durations.
+impl<const NUM: i64, const DEN: i64> TryFrom<CxxDuration<NUM, DEN>> for Duration {
+    type Error = ChronoError;
+
+    fn try_from(duration: CxxDuration<NUM, DEN>) -> Result<Self, ChronoError> {
+        let nanos = duration.as_nanos()?;
+        if nanos < 0 {
+            return Err(ChronoError::Negative);
+        }
+        let secs = u64::try_from(nanos / NANOS_PER_SEC).map_err(|_| ChronoError::Overflow)?;
+        Ok(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+    }
+}
+
+/// Truncates to whole ticks like `duration_cast`.
+impl<const NUM: i64, const DEN: i64> TryFrom<Duration> for CxxDuration<NUM, DEN> {
+    type Error = ChronoError;
+
+    fn try_from(duration: Duration) -> Result<Self, ChronoError> {
+        Self::from_nanos(duration.as_nanos() as i128)
+    }
+}
+
+/// Binding to C++ `std::chrono::system_clock::time_point`, nanoseconds since
+/// the Unix epoch. Checked in the generated code of every build.
+#[repr(transparent)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CxxSystemTime {
+    since_epoch: CxxNanoseconds,
+}
+
+impl CxxSystemTime {
+    pub const fn from_since_epoch(since_epoch: CxxNanoseconds) -> Self {
+        CxxSystemTime { since_epoch }
+    }
+
+    pub const fn since_epoch(&self) -> CxxNanoseconds {
+        self.since_epoch
+    }
+}
+
+impl TryFrom<SystemTime> for CxxSystemTime {
+    type Error = ChronoError;
+
+    fn try_from(time: SystemTime) -> Result<Self, ChronoError> {
+        let nanos = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_nanos() as i128,
+            Err(before) => -(before.duration().as_nanos() as i128),
+        };
+        CxxNanoseconds::from_nanos(nanos).map(Self::from_since_epoch)
+    }
+}
+
+impl TryFrom<CxxSystemTime> for SystemTime {
+    type Error = ChronoError;
+
+    fn try_from(time: CxxSystemTime) -> Result<Self, ChronoError> {
+        let count = time.since_epoch.count();
+        let offset = Duration::from_nanos(count.unsigned_abs());
+        if count >= 0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        }
+        .ok_or(ChronoError::Overflow)
+    }
+}
+
+/// Binding to C++ `std::chrono::steady_clock::time_point`, comparable only
+/// with other steady clock readings.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CxxSteadyInstant {
+    since_epoch: CxxNanoseconds,
+}
+
+impl CxxSteadyInstant {
+    pub fn now() -> Self {
+        extern "C" {
+            fn rxx_steady_clock_now() -> CxxSteadyInstant;
+        }
+        unsafe { rxx_steady_clock_now() }
+    }
+
+    /// `None` if `earlier` is later than `self`.
+    pub fn checked_duration_since(&self, earlier: CxxSteadyInstant) -> Option<Duration> {
+        let nanos = self.since_epoch.count() as i128 - earlier.since_epoch.count() as i128;
+        CxxNanoseconds::from_nanos(nanos).ok()?.try_into().ok()
+    }
+
+    /// Zero if `earlier` is later than `self`.
+    pub fn saturating_duration_since(&self, earlier: CxxSteadyInstant) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Self::now().saturating_duration_since(*self)
+    }
+
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        let nanos = self.since_epoch.count() as i128 + duration.as_nanos() as i128;
+        CxxNanoseconds::from_nanos(nanos)
+            .ok()
+            .map(|since_epoch| CxxSteadyInstant { since_epoch })
+    }
+
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        let nanos = self.since_epoch.count() as i128 - duration.as_nanos() as i128;
+        CxxNanoseconds::from_nanos(nanos)
+            .ok()
+            .map(|since_epoch| CxxSteadyInstant { since_epoch })
+    }
+}
@@ -0,0 +1,113 @@
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Binding to C++ `std::complex<T>`, passed by value like a C struct holding
+/// the real and imaginary parts, so it can be an argument or an atomic return
+/// of `genc_fn`. `genc_complex` checks the layout at build time.
+///
+/// `CxxVector<CxxComplex<f32>>` and `CxxVector<CxxComplex<f64>>` are
+/// provided, they deref to slices without copying.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CxxComplex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> CxxComplex<T> {
+    pub const fn new(re: T, im: T) -> Self {
+        CxxComplex { re, im }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> CxxComplex<T> {
+    pub fn conj(self) -> Self {
+        CxxComplex::new(self.re, -self.im)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> CxxComplex<T> {
+    /// Squared magnitude, like C++ `std::norm`.
+    pub fn norm(self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl<T> From<(T, T)> for CxxComplex<T> {
+    fn from((re, im): (T, T)) -> Self {
+        CxxComplex { re, im }
+    }
+}
+
+impl<T> From<CxxComplex<T>> for (T, T) {
+    fn from(c: CxxComplex<T>) -> Self {
+        (c.re, c.im)
+    }
+}
+
+impl<T: Add<Output = T>> Add for CxxComplex<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        CxxComplex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for CxxComplex<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        CxxComplex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Mul for CxxComplex<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        CxxComplex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl<T> Div for CxxComplex<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        let d = rhs.re * rhs.re + rhs.im * rhs.im;
+        CxxComplex::new(
+            (self.re * rhs.re + self.im * rhs.im) / d,
+            (self.im * rhs.re - self.re * rhs.im) / d,
+        )
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for CxxComplex<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        CxxComplex::new(-self.re, -self.im)
+    }
+}
+
+macro_rules! complex_assign_op {
+    ($tr:ident, $f:ident, $op:ident, $op_f:ident) => {
+        impl<T> $tr for CxxComplex<T>
+        where
+            CxxComplex<T>: Copy + $op<Output = CxxComplex<T>>,
+        {
+            fn $f(&mut self, rhs: Self) {
+                *self = self.$op_f(rhs);
+            }
+        }
+    };
+}
+
+complex_assign_op!(AddAssign, add_assign, Add, add);
+complex_assign_op!(SubAssign, sub_assign, Sub, sub);
+complex_assign_op!(MulAssign, mul_assign, Mul, mul);
+complex_assign_op!(DivAssign, div_assign, Div, div);
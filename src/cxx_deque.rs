@@ -0,0 +1,240 @@
+use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use std::collections::VecDeque;
+
+// implemented on the element type
+pub trait DequeElement: Sized {
+    unsafe fn __unique_drop(this: *mut c_void);
+    unsafe fn __new(out: *mut UniquePtr<CxxDeque<Self>>);
+    unsafe fn __size(this: &CxxDeque<Self>) -> usize;
+    unsafe fn __get_unchecked(this: &CxxDeque<Self>, pos: usize) -> &Self;
+    unsafe fn __get_unchecked_mut(this: &mut CxxDeque<Self>, pos: usize) -> &mut Self;
+    unsafe fn __push_back(this: &mut CxxDeque<Self>, value: &mut Self);
+    unsafe fn __push_front(this: &mut CxxDeque<Self>, value: &mut Self);
+    unsafe fn __pop_back(this: &mut CxxDeque<Self>, out: *mut Self);
+    unsafe fn __pop_front(this: &mut CxxDeque<Self>, out: *mut Self);
+    unsafe fn __clear(this: &mut CxxDeque<Self>);
+}
+
+/// Binding to C++ `std::deque<T>`.
+///
+/// Elements live in separate blocks, so unlike `CxxVector` there is no slice
+/// view, only indexing and iteration.
+#[repr(C)]
+pub struct CxxDeque<T: DequeElement> {
+    _private: [u8; 0],
+    _pd: PhantomData<T>,
+}
+
+impl<T: DequeElement> CxxDeque<T> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> UniquePtr<Self> {
+        let mut out = MaybeUninit::<UniquePtr<Self>>::uninit();
+        unsafe {
+            T::__new(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { T::__size(self) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub unsafe fn get_unchecked(&self, pos: usize) -> &T {
+        T::__get_unchecked(self, pos)
+    }
+
+    pub unsafe fn get_unchecked_mut(&mut self, pos: usize) -> &mut T {
+        T::__get_unchecked_mut(self, pos)
+    }
+
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        if pos < self.len() {
+            Some(unsafe { self.get_unchecked(pos) })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        if pos < self.len() {
+            Some(unsafe { self.get_unchecked_mut(pos) })
+        } else {
+            None
+        }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len().wrapping_sub(1))
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        // C++ calls move constructor followed by destructor on `value`
+        let mut value = ManuallyDrop::new(value);
+        unsafe { T::__push_back(self, &mut value) }
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let mut value = ManuallyDrop::new(value);
+        unsafe { T::__push_front(self, &mut value) }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut out = MaybeUninit::uninit();
+        Some(unsafe {
+            T::__pop_back(self, out.as_mut_ptr());
+            out.assume_init()
+        })
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut out = MaybeUninit::uninit();
+        Some(unsafe {
+            T::__pop_front(self, out.as_mut_ptr());
+            out.assume_init()
+        })
+    }
+
+    pub fn clear(&mut self) {
+        unsafe { T::__clear(self) }
+    }
+
+    pub fn iter(&self) -> DequeIter<'_, T> {
+        DequeIter {
+            deque: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+}
+
+pub struct DequeIter<'a, T: DequeElement> {
+    deque: &'a CxxDeque<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T: DequeElement> Iterator for DequeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(unsafe { self.deque.get_unchecked(self.front - 1) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T: DequeElement> DoubleEndedIterator for DequeIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(unsafe { self.deque.get_unchecked(self.back) })
+    }
+}
+
+impl<T: DequeElement> ExactSizeIterator for DequeIter<'_, T> {}
+
+impl<'a, T: DequeElement> IntoIterator for &'a CxxDeque<T> {
+    type Item = &'a T;
+    type IntoIter = DequeIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: DequeElement> Index<usize> for CxxDeque<T> {
+    type Output = T;
+
+    fn index(&self, pos: usize) -> &T {
+        let len = self.len();
+        self.get(pos).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {pos}")
+        })
+    }
+}
+
+impl<T: DequeElement> IndexMut<usize> for CxxDeque<T> {
+    fn index_mut(&mut self, pos: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(pos).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {pos}")
+        })
+    }
+}
+
+// never moved by Rust, same as the other node based containers
+impl<T: DequeElement> Unpin for CxxDeque<T> {}
+
+impl<T: DequeElement> UniquePtrTarget for CxxDeque<T> {
+    unsafe fn __drop(this: *mut c_void) {
+        T::__unique_drop(this)
+    }
+}
+
+impl<T: DequeElement> Default for UniquePtr<CxxDeque<T>> {
+    fn default() -> Self {
+        CxxDeque::new()
+    }
+}
+
+impl<T: DequeElement> Extend<T> for UniquePtr<CxxDeque<T>> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T: DequeElement> FromIterator<T> for UniquePtr<CxxDeque<T>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = CxxDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T: DequeElement> From<VecDeque<T>> for UniquePtr<CxxDeque<T>> {
+    fn from(deque: VecDeque<T>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+impl<T: DequeElement + Clone> From<&CxxDeque<T>> for VecDeque<T> {
+    fn from(deque: &CxxDeque<T>) -> Self {
+        deque.iter().cloned().collect()
+    }
+}
+
+impl<T: DequeElement + Debug> Debug for CxxDeque<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,104 @@
+use crate::cxx_string::CxxString;
+use crate::unique_ptr::UniquePtr;
+use core::ffi::{c_char, c_void, CStr};
+use core::fmt::{self, Debug, Display};
+use core::mem::MaybeUninit;
+use std::borrow::Cow;
+use std::io;
+
+/// Binding to C++ `std::error_code`, passed by value.
+///
+/// A function taking a trailing `std::error_code &`, or generated with
+/// `FnSig::catch_system_error`, returns `Result<T, CxxErrorCode>` when
+/// declared with that return type in `genrs_fn!`, spelled exactly
+/// `CxxErrorCode`.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CxxErrorCode {
+    value: i32,
+    // `std::error_category` singleton, compared by address like C++ does
+    category: *const c_void,
+}
+
+// categories are immutable statics
+unsafe impl Send for CxxErrorCode {}
+unsafe impl Sync for CxxErrorCode {}
+
+impl CxxErrorCode {
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// True unless the value is 0, like C++ `operator bool`.
+    pub fn is_err(&self) -> bool {
+        self.value != 0
+    }
+
+    /// Name of the category, which needs not be UTF-8 in C++.
+    pub fn category_name(&self) -> Cow<'static, str> {
+        extern "C" {
+            fn rxx_error_code_category_name(this: &CxxErrorCode) -> *const c_char;
+        }
+        let name = unsafe { CStr::from_ptr(rxx_error_code_category_name(self)) };
+        String::from_utf8_lossy(name.to_bytes())
+    }
+
+    pub fn message(&self) -> String {
+        extern "C" {
+            fn rxx_error_code_message(this: &CxxErrorCode, out: *mut UniquePtr<CxxString>);
+        }
+        let mut out = MaybeUninit::<UniquePtr<CxxString>>::uninit();
+        let message = unsafe {
+            rxx_error_code_message(self, out.as_mut_ptr());
+            out.assume_init()
+        };
+        String::from_utf8_lossy(message.as_bytes()).into_owned()
+    }
+
+    /// The errno value if the category is `generic_category` or
+    /// `system_category`.
+    pub fn raw_os_error(&self) -> Option<i32> {
+        extern "C" {
+            fn rxx_error_code_is_os_error(this: &CxxErrorCode) -> bool;
+        }
+        unsafe { rxx_error_code_is_os_error(self) }.then_some(self.value)
+    }
+}
+
+/// No error, in `system_category`.
+impl Default for CxxErrorCode {
+    fn default() -> Self {
+        extern "C" {
+            fn rxx_error_code_new() -> CxxErrorCode;
+        }
+        unsafe { rxx_error_code_new() }
+    }
+}
+
+impl Display for CxxErrorCode {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.message())
+    }
+}
+
+impl Debug for CxxErrorCode {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("CxxErrorCode")
+            .field("value", &self.value)
+            .field("category", &self.category_name())
+            .field("message", &self.message())
+            .finish()
+    }
+}
+
+impl std::error::Error for CxxErrorCode {}
+
+impl From<CxxErrorCode> for io::Error {
+    fn from(code: CxxErrorCode) -> Self {
+        match code.raw_os_error() {
+            Some(errno) => io::Error::from_raw_os_error(errno),
+            None => io::Error::other(code),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+/// Marker type naming the signature of a C++ `std::function`, declared by
+/// `genrs_function!`.
+pub trait FunctionSig: Sized {
+    /// Tuple of the argument types.
+    type Args;
+    type Output;
+    /// Boxed Rust closure with the same signature, see `CxxFunction::from_rust`.
+    type Closure: ?Sized;
+
+    unsafe fn __drop(this: &mut CxxFunction<Self>);
+    unsafe fn __clone(this: &CxxFunction<Self>, out: *mut CxxFunction<Self>);
+    unsafe fn __new_empty(out: *mut CxxFunction<Self>);
+    unsafe fn __is_empty(this: &CxxFunction<Self>) -> bool;
+    unsafe fn __call(this: &CxxFunction<Self>, args: Self::Args) -> Self::Output;
+    unsafe fn __from_rust(f: Box<Self::Closure>, out: *mut CxxFunction<Self>);
+}
+
+const FUNCTION_SIZE: usize = 4;
+
+/// Binding to C++ `std::function`, e.g. `CxxFunction<AdderFn>` for
+/// `std::function<int64_t(int64_t)>` after
+/// `genrs_function!(AdderFn, rxx_function_i64_i64, fn(x: i64) -> i64)`.
+///
+/// `genc_function` checks at build time that the layout matches. Pass it to
+/// C++ by reference, the C ABI can't pass it by value.
+#[repr(C)]
+pub struct CxxFunction<F: FunctionSig> {
+    _space: MaybeUninit<[usize; FUNCTION_SIZE]>,
+    // the target may not be thread safe
+    _pd: PhantomData<(F, *const ())>,
+}
+
+impl<F: FunctionSig> CxxFunction<F> {
+    /// A `std::function` without a target.
+    pub fn empty() -> Self {
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            F::__new_empty(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    /// Wraps a Rust closure, e.g. `Box<dyn FnMut(i64) -> i64 + Send>`.
+    ///
+    /// Copies of the `std::function` share the closure through a
+    /// `std::shared_ptr`, it is dropped with the last copy. Calls are
+    /// serialized by a mutex, so calling the same function again from inside
+    /// the closure deadlocks. A panic in the closure aborts.
+    pub fn from_rust(f: Box<F::Closure>) -> Self {
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            F::__from_rust(f, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        unsafe { F::__is_empty(self) }
+    }
+
+    /// Calls the target with a tuple of arguments, panics if empty.
+    pub fn call(&self, args: F::Args) -> F::Output {
+        if self.is_empty() {
+            panic!(
+                "called an empty CxxFunction<{}>",
+                std::any::type_name::<F>()
+            );
+        }
+        unsafe { F::__call(self, args) }
+    }
+}
+
+impl<F: FunctionSig> From<Box<F::Closure>> for CxxFunction<F> {
+    fn from(f: Box<F::Closure>) -> Self {
+        Self::from_rust(f)
+    }
+}
+
+impl<F: FunctionSig> Clone for CxxFunction<F> {
+    fn clone(&self) -> Self {
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            F::__clone(self, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+}
+
+impl<F: FunctionSig> Default for CxxFunction<F> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<F: FunctionSig> Drop for CxxFunction<F> {
+    fn drop(&mut self) {
+        unsafe { F::__drop(self) }
+    }
+}
+
+impl<F: FunctionSig> Debug for CxxFunction<F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("CxxFunction")
+            .field("empty", &self.is_empty())
+            .finish()
+    }
+}
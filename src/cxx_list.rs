@@ -0,0 +1,215 @@
+use crate::cxx_unordered_map::RawIter;
+use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use std::collections::LinkedList;
+
+// implemented on the element type
+pub trait ListElement: Sized {
+    unsafe fn __unique_drop(this: *mut c_void);
+    unsafe fn __new(out: *mut UniquePtr<CxxList<Self>>);
+    unsafe fn __size(this: &CxxList<Self>) -> usize;
+    unsafe fn __front(this: &CxxList<Self>) -> *const Self;
+    unsafe fn __back(this: &CxxList<Self>) -> *const Self;
+    unsafe fn __push_back(this: &mut CxxList<Self>, value: &mut Self);
+    unsafe fn __push_front(this: &mut CxxList<Self>, value: &mut Self);
+    unsafe fn __pop_back(this: &mut CxxList<Self>, out: *mut Self);
+    unsafe fn __pop_front(this: &mut CxxList<Self>, out: *mut Self);
+    unsafe fn __clear(this: &mut CxxList<Self>);
+    unsafe fn __splice(this: &mut CxxList<Self>, pos: usize, other: &mut CxxList<Self>);
+    unsafe fn __iter_new(this: &CxxList<Self>, begin_it: *mut RawIter, end_it: *mut RawIter);
+    unsafe fn __iter_next(it: &mut RawIter, end: &RawIter) -> *const Self;
+    unsafe fn __iter_next_back(it: &RawIter, end: &mut RawIter) -> *const Self;
+}
+
+/// Binding to C++ `std::list<T>`, opaque like `CxxUnorderedMap`.
+///
+/// Elements are only reachable from either end or by iterating, there is
+/// no slice view.
+#[repr(C)]
+pub struct CxxList<T: ListElement> {
+    _private: [u8; 0],
+    _pd: PhantomData<T>,
+}
+
+impl<T: ListElement> CxxList<T> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> UniquePtr<Self> {
+        let mut out = MaybeUninit::<UniquePtr<Self>>::uninit();
+        unsafe {
+            T::__new(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { T::__size(self) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { T::__front(self).as_ref() }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // the element is not part of the list object, mutable through `&mut self`
+        unsafe { (T::__front(self) as *mut T).as_mut() }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { T::__back(self).as_ref() }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe { (T::__back(self) as *mut T).as_mut() }
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        // C++ calls move constructor followed by destructor on `value`
+        let mut value = ManuallyDrop::new(value);
+        unsafe { T::__push_back(self, &mut value) }
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let mut value = ManuallyDrop::new(value);
+        unsafe { T::__push_front(self, &mut value) }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut out = MaybeUninit::uninit();
+        Some(unsafe {
+            T::__pop_back(self, out.as_mut_ptr());
+            out.assume_init()
+        })
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut out = MaybeUninit::uninit();
+        Some(unsafe {
+            T::__pop_front(self, out.as_mut_ptr());
+            out.assume_init()
+        })
+    }
+
+    pub fn clear(&mut self) {
+        unsafe { T::__clear(self) }
+    }
+
+    /// Moves all elements of `other` in front of the element at `pos`
+    /// without copying them, like `std::list::splice`.
+    ///
+    /// Panics if `pos > len`.
+    pub fn splice(&mut self, pos: usize, other: &mut CxxList<T>) {
+        let len = self.len();
+        assert!(
+            pos <= len,
+            "splice position {pos} is out of bounds of len {len}"
+        );
+        unsafe { T::__splice(self, pos, other) }
+    }
+
+    /// Moves all elements of `other` to the back.
+    pub fn append(&mut self, other: &mut CxxList<T>) {
+        self.splice(self.len(), other)
+    }
+
+    pub fn iter(&self) -> ListIter<'_, T> {
+        let mut it = RawIter::uninit();
+        let mut end = RawIter::uninit();
+        unsafe { T::__iter_new(self, &mut it, &mut end) };
+        ListIter {
+            it,
+            end,
+            _pd: PhantomData,
+        }
+    }
+}
+
+pub struct ListIter<'a, T: ListElement> {
+    it: RawIter,
+    end: RawIter,
+    _pd: PhantomData<&'a CxxList<T>>,
+}
+
+impl<'a, T: ListElement> Iterator for ListIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe { T::__iter_next(&mut self.it, &self.end).as_ref() }
+    }
+}
+
+impl<T: ListElement> DoubleEndedIterator for ListIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        unsafe { T::__iter_next_back(&self.it, &mut self.end).as_ref() }
+    }
+}
+
+impl<'a, T: ListElement> IntoIterator for &'a CxxList<T> {
+    type Item = &'a T;
+    type IntoIter = ListIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// never moved by Rust, the C++ list links back into its own object
+impl<T: ListElement> Unpin for CxxList<T> {}
+
+impl<T: ListElement> UniquePtrTarget for CxxList<T> {
+    unsafe fn __drop(this: *mut c_void) {
+        T::__unique_drop(this)
+    }
+}
+
+impl<T: ListElement> Default for UniquePtr<CxxList<T>> {
+    fn default() -> Self {
+        CxxList::new()
+    }
+}
+
+impl<T: ListElement> Extend<T> for UniquePtr<CxxList<T>> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T: ListElement> FromIterator<T> for UniquePtr<CxxList<T>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = CxxList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: ListElement> From<LinkedList<T>> for UniquePtr<CxxList<T>> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T: ListElement + Clone> From<&CxxList<T>> for LinkedList<T> {
+    fn from(list: &CxxList<T>) -> Self {
+        list.iter().cloned().collect()
+    }
+}
+
+impl<T: ListElement + Debug> Debug for CxxList<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
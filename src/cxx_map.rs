@@ -0,0 +1,336 @@
+use crate::cxx_unordered_map::{MapKey, RawIter};
+use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Bound, RangeBounds};
+use std::collections::BTreeMap;
+
+#[doc(hidden)]
+pub type RangeNextFn<K, V> =
+    unsafe fn(this: *const c_void, it: &mut RawIter, end: &RawIter, key: &mut *const K) -> *const V;
+
+// shims shared by std::map and std::multimap, the map is passed as `this`,
+// `MULTI` lets a key type implement both
+#[doc(hidden)]
+pub trait OrderedMapShims<V, const MULTI: bool>: Sized {
+    unsafe fn __unique_drop(this: *mut c_void);
+    unsafe fn __new(out: *mut c_void);
+    unsafe fn __len(this: *const c_void) -> usize;
+    unsafe fn __get(this: *const c_void, key: &Self) -> *const V;
+    unsafe fn __get_mut(this: *mut c_void, key: &Self) -> *mut V;
+    unsafe fn __clear(this: *mut c_void);
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn __range(
+        this: *const c_void,
+        start_kind: u8,
+        start: *const Self,
+        end_kind: u8,
+        end: *const Self,
+        begin_it: *mut RawIter,
+        end_it: *mut RawIter,
+    );
+    unsafe fn __range_next(
+        this: *const c_void,
+        it: &mut RawIter,
+        end: &RawIter,
+        key: &mut *const Self,
+    ) -> *const V;
+    unsafe fn __last(this: *const c_void, key: &mut *const Self) -> *const V;
+}
+
+// implemented on the key type
+pub trait MapElement<V>: OrderedMapShims<V, false> {
+    unsafe fn __insert(this: &mut CxxMap<Self, V>, key: &Self, value: &mut V, old: *mut V) -> bool;
+    unsafe fn __remove(this: &mut CxxMap<Self, V>, key: &Self, out: *mut V) -> bool;
+}
+
+// implemented on the key type
+pub trait MultiMapElement<V>: OrderedMapShims<V, true> {
+    unsafe fn __insert(this: &mut CxxMultiMap<Self, V>, key: &Self, value: &mut V);
+    unsafe fn __remove(this: &mut CxxMultiMap<Self, V>, key: &Self) -> usize;
+}
+
+/// Iterator over the elements of a `CxxMap` or `CxxMultiMap` in key order.
+pub struct MapRange<'a, K, V> {
+    map: *const c_void,
+    it: RawIter,
+    end: RawIter,
+    next: RangeNextFn<K, V>,
+    _pd: PhantomData<(&'a K, &'a V)>,
+}
+
+impl<'a, K, V> Iterator for MapRange<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut key = core::ptr::null();
+        unsafe {
+            let value = (self.next)(self.map, &mut self.it, &self.end, &mut key).as_ref()?;
+            Some((&*key, value))
+        }
+    }
+}
+
+// 0 for unbounded, 1 for included, 2 for excluded, see rxx::map_range
+fn with_bound<K, Q, R>(bound: Bound<&Q>, f: impl FnOnce(u8, *const K) -> R) -> R
+where
+    Q: MapKey<K> + Clone,
+{
+    match bound {
+        Bound::Unbounded => f(0, core::ptr::null()),
+        Bound::Included(key) => key.clone().with_key(|key| f(1, key)),
+        Bound::Excluded(key) => key.clone().with_key(|key| f(2, key)),
+    }
+}
+
+fn range<'a, K, V, Q, R, const MULTI: bool>(this: *const c_void, range: R) -> MapRange<'a, K, V>
+where
+    K: OrderedMapShims<V, MULTI>,
+    Q: MapKey<K> + Clone,
+    R: RangeBounds<Q>,
+{
+    let mut it = RawIter::uninit();
+    let mut end = RawIter::uninit();
+    with_bound(range.start_bound(), |start_kind, start| {
+        with_bound(range.end_bound(), |end_kind, end_key| unsafe {
+            K::__range(
+                this, start_kind, start, end_kind, end_key, &mut it, &mut end,
+            )
+        })
+    });
+
+    MapRange {
+        map: this,
+        it,
+        end,
+        next: K::__range_next,
+        _pd: PhantomData,
+    }
+}
+
+fn last<'a, K: OrderedMapShims<V, MULTI>, V, const MULTI: bool>(
+    this: *const c_void,
+) -> Option<(&'a K, &'a V)> {
+    let mut key = core::ptr::null();
+    unsafe {
+        let value = K::__last(this, &mut key).as_ref()?;
+        Some((&*key, value))
+    }
+}
+
+macro_rules! ordered_map_common {
+    ($map:ident, $elem:ident, $multi:literal) => {
+        impl<K: $elem<V>, V> $map<K, V> {
+            #[allow(clippy::new_ret_no_self)]
+            pub fn new() -> UniquePtr<Self> {
+                let mut out = MaybeUninit::<UniquePtr<Self>>::uninit();
+                unsafe {
+                    K::__new(out.as_mut_ptr().cast());
+                    out.assume_init()
+                }
+            }
+
+            fn as_void(&self) -> *const c_void {
+                self as *const Self as *const c_void
+            }
+
+            pub fn len(&self) -> usize {
+                unsafe { K::__len(self.as_void()) }
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            pub fn contains_key(&self, key: impl MapKey<K>) -> bool {
+                self.get(key).is_some()
+            }
+
+            pub fn get_mut(&mut self, key: impl MapKey<K>) -> Option<&mut V> {
+                let this = self as *mut Self as *mut c_void;
+                key.with_key(|key| unsafe { K::__get_mut(this, key).as_mut() })
+            }
+
+            pub fn clear(&mut self) {
+                unsafe { K::__clear(self as *mut Self as *mut c_void) }
+            }
+
+            pub fn iter(&self) -> MapRange<'_, K, V> {
+                range::<K, V, &K, _, $multi>(self.as_void(), ..)
+            }
+
+            /// Elements with keys in `range`, located with `lower_bound` and
+            /// `upper_bound`. Empty if the start of `range` is past its end.
+            pub fn range<Q, R>(&self, range: R) -> MapRange<'_, K, V>
+            where
+                Q: MapKey<K> + Clone,
+                R: RangeBounds<Q>,
+            {
+                self::range::<K, V, Q, R, $multi>(self.as_void(), range)
+            }
+
+            pub fn first(&self) -> Option<(&K, &V)> {
+                self.iter().next()
+            }
+
+            pub fn last(&self) -> Option<(&K, &V)> {
+                last::<K, V, $multi>(self.as_void())
+            }
+        }
+
+        // never moved by Rust, pinning elements is up to the C++ nodes
+        impl<K: $elem<V>, V> Unpin for $map<K, V> {}
+
+        impl<K: $elem<V>, V> UniquePtrTarget for $map<K, V> {
+            unsafe fn __drop(this: *mut c_void) {
+                K::__unique_drop(this)
+            }
+        }
+
+        impl<K: $elem<V>, V> Default for UniquePtr<$map<K, V>> {
+            fn default() -> Self {
+                $map::new()
+            }
+        }
+
+        impl<K: $elem<V>, V> Extend<(K, V)> for UniquePtr<$map<K, V>> {
+            fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+                for (key, value) in iter {
+                    self.insert(key, value);
+                }
+            }
+        }
+
+        impl<K: $elem<V>, V> FromIterator<(K, V)> for UniquePtr<$map<K, V>> {
+            fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+                let mut map = $map::new();
+                map.extend(iter);
+                map
+            }
+        }
+
+        impl<'a, K: $elem<V>, V> IntoIterator for &'a $map<K, V> {
+            type Item = (&'a K, &'a V);
+            type IntoIter = MapRange<'a, K, V>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        impl<K, V> Debug for $map<K, V>
+        where
+            K: $elem<V> + Debug,
+            V: Debug,
+        {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.debug_map().entries(self.iter()).finish()
+            }
+        }
+    };
+}
+
+/// Binding to C++ `std::map<K, V>`, opaque like `CxxUnorderedMap`.
+#[repr(C)]
+pub struct CxxMap<K: MapElement<V>, V> {
+    _private: [u8; 0],
+    _pd: PhantomData<(K, V)>,
+}
+
+ordered_map_common!(CxxMap, MapElement, false);
+
+impl<K: MapElement<V>, V> CxxMap<K, V> {
+    pub fn get(&self, key: impl MapKey<K>) -> Option<&V> {
+        key.with_key(|key| unsafe { K::__get(self.as_void(), key).as_ref() })
+    }
+
+    /// Copies `key` into the map, returns the value it replaced if any.
+    pub fn insert(&mut self, key: impl MapKey<K>, value: V) -> Option<V> {
+        // C++ calls move constructor followed by destructor on `value`
+        let mut value = ManuallyDrop::new(value);
+        let mut old = MaybeUninit::<V>::uninit();
+        key.with_key(|key| unsafe {
+            if K::__insert(self, key, &mut value, old.as_mut_ptr()) {
+                Some(old.assume_init())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn remove(&mut self, key: impl MapKey<K>) -> Option<V> {
+        let mut out = MaybeUninit::<V>::uninit();
+        key.with_key(|key| unsafe {
+            if K::__remove(self, key, out.as_mut_ptr()) {
+                Some(out.assume_init())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<K, V> From<&CxxMap<K, V>> for BTreeMap<K, V>
+where
+    K: MapElement<V> + Clone + Ord,
+    V: Clone,
+{
+    fn from(map: &CxxMap<K, V>) -> Self {
+        map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl<K: MapElement<V>, V> From<BTreeMap<K, V>> for UniquePtr<CxxMap<K, V>> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+/// Binding to C++ `std::multimap<K, V>`, elements with equal keys keep their
+/// insertion order.
+#[repr(C)]
+pub struct CxxMultiMap<K: MultiMapElement<V>, V> {
+    _private: [u8; 0],
+    _pd: PhantomData<(K, V)>,
+}
+
+ordered_map_common!(CxxMultiMap, MultiMapElement, true);
+
+impl<K: MultiMapElement<V>, V> CxxMultiMap<K, V> {
+    /// First value inserted with `key`, found with `lower_bound`.
+    pub fn get(&self, key: impl MapKey<K>) -> Option<&V> {
+        key.with_key(|key| unsafe { K::__get(self.as_void(), key).as_ref() })
+    }
+
+    /// All values with `key`, like `equal_range`.
+    pub fn get_all<Q: MapKey<K> + Clone>(&self, key: Q) -> impl Iterator<Item = &V> {
+        self.range(key.clone()..=key).map(|(_, v)| v)
+    }
+
+    pub fn insert(&mut self, key: impl MapKey<K>, value: V) {
+        // C++ calls move constructor followed by destructor on `value`
+        let mut value = ManuallyDrop::new(value);
+        key.with_key(|key| unsafe { K::__insert(self, key, &mut value) })
+    }
+
+    /// Removes all values with `key`, returns how many there were.
+    pub fn remove(&mut self, key: impl MapKey<K>) -> usize {
+        key.with_key(|key| unsafe { K::__remove(self, key) })
+    }
+}
+
+impl<K, V> From<&CxxMultiMap<K, V>> for BTreeMap<K, Vec<V>>
+where
+    K: MultiMapElement<V> + Clone + Ord,
+    V: Clone,
+{
+    fn from(map: &CxxMultiMap<K, V>) -> Self {
+        let mut out = BTreeMap::<K, Vec<V>>::new();
+        for (k, v) in map {
+            out.entry(k.clone()).or_default().push(v.clone());
+        }
+        out
+    }
+}
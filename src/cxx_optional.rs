@@ -0,0 +1,105 @@
+use core::fmt::{self, Debug};
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+pub trait OptionalElement: Sized {
+    unsafe fn __drop(this: &mut CxxOptional<Self>);
+    unsafe fn __new_none(out: *mut CxxOptional<Self>);
+    unsafe fn __new(value: &mut Self, out: *mut CxxOptional<Self>);
+    unsafe fn __get(this: &CxxOptional<Self>) -> *const Self;
+    unsafe fn __get_mut(this: &mut CxxOptional<Self>) -> *mut Self;
+    unsafe fn __take(this: &mut CxxOptional<Self>, out: *mut Self) -> bool;
+}
+
+/// Binding to C++ `std::optional<T>`, needs C++17.
+///
+/// `genc_optional` checks at build time that `std::optional<T>` is laid out as
+/// `T` followed by a flag, `T` must have the same size in Rust and C++.
+#[repr(C)]
+pub struct CxxOptional<T: OptionalElement> {
+    _value: MaybeUninit<T>,
+    _engaged: MaybeUninit<bool>,
+}
+
+impl<T: OptionalElement> CxxOptional<T> {
+    pub fn none() -> Self {
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            T::__new_none(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn some(value: T) -> Self {
+        // C++ calls move constructor followed by destructor on `value`
+        let mut value = ManuallyDrop::new(value);
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            T::__new(&mut value, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn is_some(&self) -> bool {
+        self.as_option().is_some()
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.as_option().is_none()
+    }
+
+    pub fn as_option(&self) -> Option<&T> {
+        unsafe { T::__get(self).as_ref() }
+    }
+
+    pub fn as_option_mut(&mut self) -> Option<&mut T> {
+        unsafe { T::__get_mut(self).as_mut() }
+    }
+
+    /// Moves the value out, leaving `self` empty.
+    pub fn take(&mut self) -> Option<T> {
+        let mut out = MaybeUninit::<T>::uninit();
+        unsafe {
+            if T::__take(self, out.as_mut_ptr()) {
+                Some(out.assume_init())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl<T: OptionalElement> Default for CxxOptional<T> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<T: OptionalElement> From<Option<T>> for CxxOptional<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::some(value),
+            None => Self::none(),
+        }
+    }
+}
+
+impl<T: OptionalElement> From<CxxOptional<T>> for Option<T> {
+    fn from(mut value: CxxOptional<T>) -> Self {
+        value.take()
+    }
+}
+
+impl<T: OptionalElement> Drop for CxxOptional<T> {
+    fn drop(&mut self) {
+        unsafe { T::__drop(self) }
+    }
+}
+
+impl<T: OptionalElement + Debug> Debug for CxxOptional<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_option() {
+            None => formatter.write_str("nullopt"),
+            Some(value) => Debug::fmt(value, formatter),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::cxx_vector::CxxVector;
+use crate::unique_ptr::UniquePtr;
+use core::ffi::c_char;
+use core::fmt::{self, Debug};
+use core::marker::{PhantomData, PhantomPinned};
+use core::mem::MaybeUninit;
+use core::slice;
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+const PATH_SIZE: usize = 5; // libstdc++, std::string plus the component list
+
+/// Binding to C++ `std::filesystem::path`, needs C++17.
+///
+/// The path is kept as raw OS bytes like `Path` on Unix. It has a
+/// `std::string` inside, so like `CxxString` Rust never moves it, it is
+/// created in a `UniquePtr` or a `CxxVector`.
+#[repr(C)]
+pub struct CxxPath {
+    _space: MaybeUninit<[usize; PATH_SIZE]>,
+    _pin: PhantomData<PhantomPinned>,
+}
+
+impl CxxPath {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(path: &Path) -> UniquePtr<Self> {
+        extern "C" {
+            fn rxx_path_new(ptr: *const u8, len: usize, out: *mut UniquePtr<CxxPath>);
+        }
+        let bytes = path.as_os_str().as_bytes();
+        let mut out = MaybeUninit::<UniquePtr<Self>>::uninit();
+        unsafe {
+            rxx_path_new(bytes.as_ptr(), bytes.len(), out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn as_path(&self) -> &Path {
+        extern "C" {
+            fn rxx_path_native(this: &CxxPath, len: &mut usize) -> *const c_char;
+        }
+        let mut len = 0;
+        unsafe {
+            let ptr = rxx_path_native(self, &mut len);
+            Path::new(OsStr::from_bytes(slice::from_raw_parts(
+                ptr as *const u8,
+                len,
+            )))
+        }
+    }
+
+    pub fn to_path_buf(&self) -> PathBuf {
+        self.as_path().to_path_buf()
+    }
+
+    /// Appends with C++ `operator/`, which replaces the path if `path` is
+    /// absolute, same as `Path::join`.
+    pub fn join(&self, path: &Path) -> UniquePtr<CxxPath> {
+        extern "C" {
+            fn rxx_path_join(
+                this: &CxxPath,
+                ptr: *const u8,
+                len: usize,
+                out: *mut UniquePtr<CxxPath>,
+            );
+        }
+        let bytes = path.as_os_str().as_bytes();
+        let mut out = MaybeUninit::<UniquePtr<Self>>::uninit();
+        unsafe {
+            rxx_path_join(self, bytes.as_ptr(), bytes.len(), out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+}
+
+impl AsRef<Path> for CxxPath {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl PartialEq for CxxPath {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_path() == other.as_path()
+    }
+}
+
+impl Eq for CxxPath {}
+
+impl Debug for CxxPath {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.as_path().fmt(formatter)
+    }
+}
+
+impl From<&Path> for UniquePtr<CxxPath> {
+    fn from(path: &Path) -> Self {
+        CxxPath::new(path)
+    }
+}
+
+impl CxxVector<CxxPath> {
+    /// Appends a copy of `path`, in place of `push` which would move a
+    /// `CxxPath`.
+    pub fn push_path(&mut self, path: &Path) {
+        extern "C" {
+            fn rxx_vector_path_push(this: &mut CxxVector<CxxPath>, ptr: *const u8, len: usize);
+        }
+        let bytes = path.as_os_str().as_bytes();
+        unsafe { rxx_vector_path_push(self, bytes.as_ptr(), bytes.len()) }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::cxx_unordered_map::{MapKey, RawIter};
+use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use std::collections::{BTreeSet, HashSet};
+
+// implemented on the element type
+pub trait SetElement: Sized {
+    unsafe fn __unique_drop(this: *mut c_void);
+    unsafe fn __new(out: *mut UniquePtr<CxxSet<Self>>);
+    unsafe fn __len(this: &CxxSet<Self>) -> usize;
+    unsafe fn __contains(this: &CxxSet<Self>, value: &Self) -> bool;
+    unsafe fn __insert(this: &mut CxxSet<Self>, value: &mut Self) -> bool;
+    unsafe fn __remove(this: &mut CxxSet<Self>, value: &Self) -> bool;
+    unsafe fn __clear(this: &mut CxxSet<Self>);
+    unsafe fn __iter_new(this: &CxxSet<Self>, it: *mut RawIter);
+    unsafe fn __iter_next(this: &CxxSet<Self>, it: &mut RawIter) -> *const Self;
+}
+
+// implemented on the element type
+pub trait UnorderedSetElement: Sized {
+    unsafe fn __unique_drop(this: *mut c_void);
+    unsafe fn __new(out: *mut UniquePtr<CxxUnorderedSet<Self>>);
+    unsafe fn __len(this: &CxxUnorderedSet<Self>) -> usize;
+    unsafe fn __contains(this: &CxxUnorderedSet<Self>, value: &Self) -> bool;
+    unsafe fn __insert(this: &mut CxxUnorderedSet<Self>, value: &mut Self) -> bool;
+    unsafe fn __remove(this: &mut CxxUnorderedSet<Self>, value: &Self) -> bool;
+    unsafe fn __clear(this: &mut CxxUnorderedSet<Self>);
+    unsafe fn __reserve(this: &mut CxxUnorderedSet<Self>, n: usize);
+    unsafe fn __iter_new(this: &CxxUnorderedSet<Self>, it: *mut RawIter);
+    unsafe fn __iter_next(this: &CxxUnorderedSet<Self>, it: &mut RawIter) -> *const Self;
+}
+
+macro_rules! set_common {
+    ($set:ident, $elem:ident, $iter:ident) => {
+        impl<T: $elem> $set<T> {
+            #[allow(clippy::new_ret_no_self)]
+            pub fn new() -> UniquePtr<Self> {
+                let mut out = MaybeUninit::<UniquePtr<Self>>::uninit();
+                unsafe {
+                    T::__new(out.as_mut_ptr());
+                    out.assume_init()
+                }
+            }
+
+            pub fn len(&self) -> usize {
+                unsafe { T::__len(self) }
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            pub fn contains(&self, value: impl MapKey<T>) -> bool {
+                value.with_key(|value| unsafe { T::__contains(self, value) })
+            }
+
+            /// Returns whether `value` was newly inserted.
+            pub fn insert(&mut self, value: T) -> bool {
+                // C++ calls move constructor followed by destructor on `value`
+                let mut value = ManuallyDrop::new(value);
+                unsafe { T::__insert(self, &mut value) }
+            }
+
+            /// Returns whether `value` was present.
+            pub fn remove(&mut self, value: impl MapKey<T>) -> bool {
+                value.with_key(|value| unsafe { T::__remove(self, value) })
+            }
+
+            pub fn clear(&mut self) {
+                unsafe { T::__clear(self) }
+            }
+
+            pub fn iter(&self) -> $iter<'_, T> {
+                let mut it = RawIter::uninit();
+                unsafe { T::__iter_new(self, &mut it) };
+                $iter { set: self, it }
+            }
+        }
+
+        pub struct $iter<'a, T: $elem> {
+            set: &'a $set<T>,
+            it: RawIter,
+        }
+
+        impl<'a, T: $elem> Iterator for $iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                unsafe { T::__iter_next(self.set, &mut self.it).as_ref() }
+            }
+        }
+
+        impl<'a, T: $elem> IntoIterator for &'a $set<T> {
+            type Item = &'a T;
+            type IntoIter = $iter<'a, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        // never moved by Rust, pinning elements is up to the C++ nodes
+        impl<T: $elem> Unpin for $set<T> {}
+
+        impl<T: $elem> UniquePtrTarget for $set<T> {
+            unsafe fn __drop(this: *mut c_void) {
+                T::__unique_drop(this)
+            }
+        }
+
+        impl<T: $elem> Default for UniquePtr<$set<T>> {
+            fn default() -> Self {
+                $set::new()
+            }
+        }
+
+        impl<T: $elem> Extend<T> for UniquePtr<$set<T>> {
+            fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+                for value in iter {
+                    self.insert(value);
+                }
+            }
+        }
+
+        impl<T: $elem> FromIterator<T> for UniquePtr<$set<T>> {
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                let mut set = $set::new();
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl<T: $elem + Debug> Debug for $set<T> {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.debug_set().entries(self.iter()).finish()
+            }
+        }
+    };
+}
+
+/// Binding to C++ `std::set<T>`, opaque like `CxxUnorderedMap`. Iterates in
+/// ascending order.
+#[repr(C)]
+pub struct CxxSet<T: SetElement> {
+    _private: [u8; 0],
+    _pd: PhantomData<T>,
+}
+
+set_common!(CxxSet, SetElement, SetIter);
+
+impl<T: SetElement + Clone + Ord> From<&CxxSet<T>> for BTreeSet<T> {
+    fn from(set: &CxxSet<T>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
+impl<T: SetElement> From<BTreeSet<T>> for UniquePtr<CxxSet<T>> {
+    fn from(set: BTreeSet<T>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+/// Binding to C++ `std::unordered_set<T>`, opaque like `CxxUnorderedMap`.
+#[repr(C)]
+pub struct CxxUnorderedSet<T: UnorderedSetElement> {
+    _private: [u8; 0],
+    _pd: PhantomData<T>,
+}
+
+set_common!(CxxUnorderedSet, UnorderedSetElement, UnorderedSetIter);
+
+impl<T: UnorderedSetElement> CxxUnorderedSet<T> {
+    /// Makes room for at least `n` elements in total.
+    pub fn reserve(&mut self, n: usize) {
+        unsafe { T::__reserve(self, n) }
+    }
+}
+
+impl<T: UnorderedSetElement + Clone + Eq + Hash> From<&CxxUnorderedSet<T>> for HashSet<T> {
+    fn from(set: &CxxUnorderedSet<T>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
+impl<T: UnorderedSetElement> From<HashSet<T>> for UniquePtr<CxxUnorderedSet<T>> {
+    fn from(set: HashSet<T>) -> Self {
+        let mut out = CxxUnorderedSet::new();
+        out.reserve(set.len());
+        out.extend(set);
+        out
+    }
+}
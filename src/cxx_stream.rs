@@ -0,0 +1,222 @@
+use crate::cxx_string::CxxString;
+use crate::unique_ptr::UniquePtr;
+use core::ffi::c_int;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::slice;
+use std::io::{self, BufRead, Read, Write};
+
+// stream state bits passed by the shims
+const BADBIT: c_int = 1;
+const FAILBIT: c_int = 2;
+const EOFBIT: c_int = 4;
+
+fn state_error(state: c_int) -> io::Error {
+    let bit = if state & BADBIT != 0 {
+        "badbit"
+    } else {
+        "failbit"
+    };
+    io::Error::other(format!("C++ stream error, {bit} is set"))
+}
+
+macro_rules! stream_state {
+    ($stream:ident, $state:ident, $clear:ident) => {
+        impl $stream {
+            fn state(&self) -> c_int {
+                extern "C" {
+                    fn $state(this: &$stream) -> c_int;
+                }
+                unsafe { $state(self) }
+            }
+
+            pub fn good(&self) -> bool {
+                self.state() == 0
+            }
+
+            pub fn eof(&self) -> bool {
+                self.state() & EOFBIT != 0
+            }
+
+            /// True if `failbit` or `badbit` is set, like C++ `fail()`.
+            pub fn fail(&self) -> bool {
+                self.state() & (FAILBIT | BADBIT) != 0
+            }
+
+            pub fn bad(&self) -> bool {
+                self.state() & BADBIT != 0
+            }
+
+            /// Resets the state to good, which is sticky in C++.
+            pub fn clear(&mut self) {
+                extern "C" {
+                    fn $clear(this: &mut $stream);
+                }
+                unsafe { $clear(self) }
+            }
+        }
+    };
+}
+
+/// Binding to C++ `std::istream`, only used by reference.
+///
+/// Reaching the end is not an error, any other `failbit` or `badbit` is
+/// reported as `io::ErrorKind::Other`. `BufRead` reads straight from the get
+/// area of the `std::streambuf` and fails on unbuffered streams.
+#[repr(C)]
+pub struct CxxIStream {
+    _private: [u8; 0],
+    // C++ streams are not thread safe
+    _pd: PhantomData<*mut ()>,
+}
+
+stream_state!(CxxIStream, rxx_istream_state, rxx_istream_clear);
+
+impl Read for CxxIStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        extern "C" {
+            fn rxx_istream_read(
+                this: &mut CxxIStream,
+                buf: *mut u8,
+                len: usize,
+                state: &mut c_int,
+            ) -> usize;
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut state = 0;
+        let n = unsafe { rxx_istream_read(self, buf.as_mut_ptr(), buf.len(), &mut state) };
+        // `read` sets failbit along with eofbit on a short read
+        if state & BADBIT != 0 || (state & FAILBIT != 0 && state & EOFBIT == 0) {
+            return Err(state_error(state));
+        }
+        Ok(n)
+    }
+}
+
+impl BufRead for CxxIStream {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        extern "C" {
+            fn rxx_istream_fill_buf(
+                this: &mut CxxIStream,
+                len: &mut usize,
+                state: &mut c_int,
+            ) -> *const u8;
+        }
+        let mut len = 0;
+        let mut state = 0;
+        let data = unsafe { rxx_istream_fill_buf(self, &mut len, &mut state) };
+        if state & EOFBIT != 0 && state & BADBIT == 0 {
+            return Ok(&[]);
+        }
+        if state != 0 {
+            return Err(state_error(state));
+        }
+        if data.is_null() || len == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "C++ stream has no get area",
+            ));
+        }
+        Ok(unsafe { slice::from_raw_parts(data, len) })
+    }
+
+    fn consume(&mut self, amt: usize) {
+        extern "C" {
+            fn rxx_istream_consume(this: &mut CxxIStream, n: usize);
+        }
+        if amt > 0 {
+            unsafe { rxx_istream_consume(self, amt) }
+        }
+    }
+}
+
+/// Binding to C++ `std::ostream`, only used by reference. A set `failbit`
+/// or `badbit` is reported as `io::ErrorKind::Other`.
+#[repr(C)]
+pub struct CxxOStream {
+    _private: [u8; 0],
+    _pd: PhantomData<*mut ()>,
+}
+
+stream_state!(CxxOStream, rxx_ostream_state, rxx_ostream_clear);
+
+impl Write for CxxOStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        extern "C" {
+            fn rxx_ostream_write(this: &mut CxxOStream, buf: *const u8, len: usize) -> c_int;
+        }
+        match unsafe { rxx_ostream_write(self, buf.as_ptr(), buf.len()) } {
+            state if state & (FAILBIT | BADBIT) != 0 => Err(state_error(state)),
+            _ => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        extern "C" {
+            fn rxx_ostream_flush(this: &mut CxxOStream) -> c_int;
+        }
+        match unsafe { rxx_ostream_flush(self) } {
+            state if state & (FAILBIT | BADBIT) != 0 => Err(state_error(state)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Binding to C++ `std::stringstream`, an in-memory stream to pass to C++
+/// as `std::istream &` or `std::ostream &`.
+#[repr(C)]
+pub struct CxxStringStream {
+    _private: [u8; 0],
+    _pd: PhantomData<*mut ()>,
+}
+
+impl CxxStringStream {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> UniquePtr<Self> {
+        Self::from_bytes(&[])
+    }
+
+    /// Starts with `bytes` to read, writing overwrites them from the start.
+    pub fn from_bytes(bytes: &[u8]) -> UniquePtr<Self> {
+        extern "C" {
+            fn rxx_stringstream_new(
+                buf: *const u8,
+                len: usize,
+                out: *mut UniquePtr<CxxStringStream>,
+            );
+        }
+        let mut out = MaybeUninit::<UniquePtr<Self>>::uninit();
+        unsafe {
+            rxx_stringstream_new(bytes.as_ptr(), bytes.len(), out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn as_istream(&mut self) -> &mut CxxIStream {
+        extern "C" {
+            fn rxx_stringstream_istream(this: &mut CxxStringStream) -> &mut CxxIStream;
+        }
+        unsafe { rxx_stringstream_istream(self) }
+    }
+
+    pub fn as_ostream(&mut self) -> &mut CxxOStream {
+        extern "C" {
+            fn rxx_stringstream_ostream(this: &mut CxxStringStream) -> &mut CxxOStream;
+        }
+        unsafe { rxx_stringstream_ostream(self) }
+    }
+
+    /// Copy of the whole buffer, like `str()`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        extern "C" {
+            fn rxx_stringstream_str(this: &CxxStringStream, out: *mut UniquePtr<CxxString>);
+        }
+        let mut out = MaybeUninit::<UniquePtr<CxxString>>::uninit();
+        unsafe {
+            rxx_stringstream_str(self, out.as_mut_ptr());
+            out.assume_init().as_bytes().to_vec()
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+/// Binding to C++ `std::pair<A, B>`, `genc_pair` checks at build time that the
+/// members are laid out like this struct.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CxxPair<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<A, B> CxxPair<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        CxxPair { first, second }
+    }
+}
+
+impl<A: Unpin, B: Unpin> From<(A, B)> for CxxPair<A, B> {
+    fn from((first, second): (A, B)) -> Self {
+        CxxPair { first, second }
+    }
+}
+
+impl<A: Unpin, B: Unpin> From<CxxPair<A, B>> for (A, B) {
+    fn from(pair: CxxPair<A, B>) -> Self {
+        (pair.first, pair.second)
+    }
+}
+
+/// Rust tuple mirroring the elements of a C++ `std::tuple`, implemented by
+/// `genrs_tuple!`.
+pub trait TupleElements: Sized {
+    /// Same size and alignment as the C++ tuple, checked by `genc_tuple`.
+    type Layout;
+    type Refs<'a>
+    where
+        Self: 'a;
+    type MutRefs<'a>
+    where
+        Self: 'a;
+
+    unsafe fn __drop(this: &mut CxxTuple<Self>);
+    unsafe fn __new(value: Self, out: *mut CxxTuple<Self>);
+    unsafe fn __take(this: &mut CxxTuple<Self>, out: *mut Self);
+    unsafe fn __as_refs(this: &CxxTuple<Self>) -> Self::Refs<'_>;
+    unsafe fn __as_mut_refs(this: &mut CxxTuple<Self>) -> Self::MutRefs<'_>;
+}
+
+/// Binding to C++ `std::tuple`, elements are accessed through C++ since the
+/// standard library is free to store them in any order.
+#[repr(C)]
+pub struct CxxTuple<T: TupleElements> {
+    _space: MaybeUninit<T::Layout>,
+}
+
+impl<T: TupleElements> CxxTuple<T> {
+    pub fn new(value: T) -> Self {
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            T::__new(value, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    /// References to each element, in order.
+    pub fn as_refs(&self) -> T::Refs<'_> {
+        unsafe { T::__as_refs(self) }
+    }
+
+    pub fn as_mut_refs(&mut self) -> T::MutRefs<'_>
+    where
+        T: Unpin,
+    {
+        unsafe { T::__as_mut_refs(self) }
+    }
+
+    pub fn into_tuple(self) -> T
+    where
+        T: Unpin,
+    {
+        // C++ moves out the elements and destroys the tuple
+        let mut this = ManuallyDrop::new(self);
+        let mut out = MaybeUninit::<T>::uninit();
+        unsafe {
+            T::__take(&mut this, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+}
+
+impl<T: TupleElements + Unpin> From<T> for CxxTuple<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: TupleElements> Drop for CxxTuple<T> {
+    fn drop(&mut self) {
+        unsafe { T::__drop(self) }
+    }
+}
+
+macro_rules! tuple_layout {
+    ($layout:ident, $($tp:ident),+) => {
+        #[doc(hidden)]
+        #[repr(C)]
+        pub struct $layout<$($tp),+>($($tp),+);
+
+        impl<$($tp),+> From<CxxTuple<($($tp),+)>> for ($($tp),+)
+        where
+            ($($tp),+): TupleElements + Unpin,
+        {
+            fn from(tuple: CxxTuple<($($tp),+)>) -> Self {
+                tuple.into_tuple()
+            }
+        }
+    };
+}
+
+tuple_layout!(TupleLayout2, A, B);
+tuple_layout!(TupleLayout3, A, B, C);
+tuple_layout!(TupleLayout4, A, B, C, D);
+tuple_layout!(TupleLayout5, A, B, C, D, E);
+tuple_layout!(TupleLayout6, A, B, C, D, E, F);
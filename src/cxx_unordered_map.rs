@@ -0,0 +1,239 @@
+use crate::cxx_string::{CxxString, StackString};
+use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
+use core::ffi::c_void;
+use core::fmt::{self, Debug};
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use std::collections::HashMap;
+
+/// Key accepted by map lookups, lets `&str` stand in for `CxxString` keys.
+pub trait MapKey<K> {
+    fn with_key<R>(self, f: impl FnOnce(&K) -> R) -> R;
+}
+
+impl<K> MapKey<K> for K {
+    fn with_key<R>(self, f: impl FnOnce(&K) -> R) -> R {
+        f(&self)
+    }
+}
+
+impl<K> MapKey<K> for &K {
+    fn with_key<R>(self, f: impl FnOnce(&K) -> R) -> R {
+        f(self)
+    }
+}
+
+impl MapKey<CxxString> for &str {
+    fn with_key<R>(self, f: impl FnOnce(&CxxString) -> R) -> R {
+        let mut key = StackString::default();
+        f(&key.init(self))
+    }
+}
+
+/// Storage for a C++ iterator, checked to fit by the generated C++ code.
+#[doc(hidden)]
+#[repr(C)]
+pub struct RawIter {
+    _space: MaybeUninit<[usize; 2]>,
+}
+
+impl RawIter {
+    pub(crate) fn uninit() -> Self {
+        RawIter {
+            _space: MaybeUninit::uninit(),
+        }
+    }
+}
+
+// implemented on the key type
+pub trait UnorderedMapElement<V>: Sized {
+    unsafe fn __unique_drop(this: *mut c_void);
+    unsafe fn __new(out: *mut UniquePtr<CxxUnorderedMap<Self, V>>);
+    unsafe fn __len(this: &CxxUnorderedMap<Self, V>) -> usize;
+    unsafe fn __get(this: &CxxUnorderedMap<Self, V>, key: &Self) -> *const V;
+    unsafe fn __get_mut(this: &mut CxxUnorderedMap<Self, V>, key: &Self) -> *mut V;
+    unsafe fn __insert(
+        this: &mut CxxUnorderedMap<Self, V>,
+        key: &Self,
+        value: &mut V,
+        old: *mut V,
+    ) -> bool;
+    unsafe fn __remove(this: &mut CxxUnorderedMap<Self, V>, key: &Self, out: *mut V) -> bool;
+    unsafe fn __clear(this: &mut CxxUnorderedMap<Self, V>);
+    unsafe fn __reserve(this: &mut CxxUnorderedMap<Self, V>, n: usize);
+    unsafe fn __iter_new(this: &CxxUnorderedMap<Self, V>, it: *mut RawIter);
+    unsafe fn __iter_next(
+        this: &CxxUnorderedMap<Self, V>,
+        it: &mut RawIter,
+        key: &mut *const Self,
+    ) -> *const V;
+}
+
+/// Binding to C++ `std::unordered_map<K, V>`.
+///
+/// libstdc++ keeps pointers into the map object itself, so it is opaque to
+/// Rust and lives behind a reference or `UniquePtr`.
+#[repr(C)]
+pub struct CxxUnorderedMap<K: UnorderedMapElement<V>, V> {
+    _private: [u8; 0],
+    _pd: PhantomData<(K, V)>,
+}
+
+impl<K: UnorderedMapElement<V>, V> CxxUnorderedMap<K, V> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> UniquePtr<Self> {
+        let mut out = MaybeUninit::<UniquePtr<Self>>::uninit();
+        unsafe {
+            K::__new(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { K::__len(self) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, key: impl MapKey<K>) -> Option<&V> {
+        key.with_key(|key| unsafe { K::__get(self, key).as_ref() })
+    }
+
+    pub fn get_mut(&mut self, key: impl MapKey<K>) -> Option<&mut V> {
+        key.with_key(|key| unsafe { K::__get_mut(self, key).as_mut() })
+    }
+
+    pub fn contains_key(&self, key: impl MapKey<K>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Copies `key` into the map, returns the value it replaced if any.
+    pub fn insert(&mut self, key: impl MapKey<K>, value: V) -> Option<V> {
+        // C++ calls move constructor followed by destructor on `value`
+        let mut value = ManuallyDrop::new(value);
+        let mut old = MaybeUninit::<V>::uninit();
+        key.with_key(|key| unsafe {
+            if K::__insert(self, key, &mut value, old.as_mut_ptr()) {
+                Some(old.assume_init())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn remove(&mut self, key: impl MapKey<K>) -> Option<V> {
+        let mut out = MaybeUninit::<V>::uninit();
+        key.with_key(|key| unsafe {
+            if K::__remove(self, key, out.as_mut_ptr()) {
+                Some(out.assume_init())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn clear(&mut self) {
+        unsafe { K::__clear(self) }
+    }
+
+    /// Makes room for at least `n` elements in total.
+    pub fn reserve(&mut self, n: usize) {
+        unsafe { K::__reserve(self, n) }
+    }
+
+    /// Iterates in the unspecified order of the C++ buckets.
+    pub fn iter(&self) -> UnorderedMapIter<'_, K, V> {
+        let mut it = RawIter::uninit();
+        unsafe { K::__iter_new(self, &mut it) };
+        UnorderedMapIter { map: self, it }
+    }
+}
+
+pub struct UnorderedMapIter<'a, K: UnorderedMapElement<V>, V> {
+    map: &'a CxxUnorderedMap<K, V>,
+    it: RawIter,
+}
+
+impl<'a, K: UnorderedMapElement<V>, V> Iterator for UnorderedMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut key = core::ptr::null();
+        unsafe {
+            let value = K::__iter_next(self.map, &mut self.it, &mut key).as_ref()?;
+            Some((&*key, value))
+        }
+    }
+}
+
+impl<'a, K: UnorderedMapElement<V>, V> IntoIterator for &'a CxxUnorderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = UnorderedMapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// never moved by Rust, pinning elements is up to the C++ nodes
+impl<K: UnorderedMapElement<V>, V> Unpin for CxxUnorderedMap<K, V> {}
+
+impl<K: UnorderedMapElement<V>, V> UniquePtrTarget for CxxUnorderedMap<K, V> {
+    unsafe fn __drop(this: *mut c_void) {
+        K::__unique_drop(this)
+    }
+}
+
+impl<K: UnorderedMapElement<V>, V> Default for UniquePtr<CxxUnorderedMap<K, V>> {
+    fn default() -> Self {
+        CxxUnorderedMap::new()
+    }
+}
+
+impl<K: UnorderedMapElement<V>, V> Extend<(K, V)> for UniquePtr<CxxUnorderedMap<K, V>> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: UnorderedMapElement<V>, V> FromIterator<(K, V)> for UniquePtr<CxxUnorderedMap<K, V>> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = CxxUnorderedMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: UnorderedMapElement<V>, V> From<HashMap<K, V>> for UniquePtr<CxxUnorderedMap<K, V>> {
+    fn from(map: HashMap<K, V>) -> Self {
+        let mut out = CxxUnorderedMap::new();
+        out.reserve(map.len());
+        out.extend(map);
+        out
+    }
+}
+
+impl<K, V> From<&CxxUnorderedMap<K, V>> for HashMap<K, V>
+where
+    K: UnorderedMapElement<V> + Clone + Eq + Hash,
+    V: Clone,
+{
+    fn from(map: &CxxUnorderedMap<K, V>) -> Self {
+        map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl<K, V> Debug for CxxUnorderedMap<K, V>
+where
+    K: UnorderedMapElement<V> + Debug,
+    V: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,80 @@
+use core::mem::MaybeUninit;
+
+/// Rust enum mirroring the alternatives of a C++ `std::variant`, implemented
+/// by `genrs_variant!`.
+pub trait VariantEnum: Sized {
+    /// Union of all alternatives, gives `CxxVariant` its size and alignment.
+    type Storage;
+    /// Borrowed view with one reference per alternative.
+    type Ref<'a>
+    where
+        Self: 'a;
+
+    unsafe fn __drop(this: &mut CxxVariant<Self>);
+    unsafe fn __index(this: &CxxVariant<Self>) -> usize;
+    unsafe fn __as_enum(this: &CxxVariant<Self>) -> Self::Ref<'_>;
+    unsafe fn __new(value: Self, out: *mut CxxVariant<Self>);
+    unsafe fn __emplace(this: &mut CxxVariant<Self>, value: Self);
+}
+
+/// Binding to C++ `std::variant`, needs C++17.
+///
+/// `genc_variant` checks at build time that the variant is laid out as the
+/// union of its alternatives followed by a one byte index.
+#[repr(C)]
+pub struct CxxVariant<E: VariantEnum> {
+    _storage: MaybeUninit<E::Storage>,
+    _index: MaybeUninit<u8>,
+}
+
+impl<E: VariantEnum> CxxVariant<E> {
+    pub fn new(value: E) -> Self {
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            E::__new(value, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    /// Index of the held alternative, `None` if valueless by exception.
+    pub fn index(&self) -> Option<usize> {
+        let index = unsafe { E::__index(self) };
+        if index == usize::MAX {
+            None
+        } else {
+            Some(index)
+        }
+    }
+
+    pub fn is_valueless(&self) -> bool {
+        self.index().is_none()
+    }
+
+    /// Panics if valueless by exception.
+    pub fn as_enum(&self) -> E::Ref<'_> {
+        if self.is_valueless() {
+            panic!(
+                "called as_enum on a valueless CxxVariant<{}>",
+                std::any::type_name::<E>(),
+            );
+        }
+        unsafe { E::__as_enum(self) }
+    }
+
+    /// Destroys the held alternative and replaces it with `value`.
+    pub fn emplace(&mut self, value: E) {
+        unsafe { E::__emplace(self, value) }
+    }
+}
+
+impl<E: VariantEnum> From<E> for CxxVariant<E> {
+    fn from(value: E) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<E: VariantEnum> Drop for CxxVariant<E> {
+    fn drop(&mut self) {
+        unsafe { E::__drop(self) }
+    }
+}
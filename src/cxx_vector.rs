@@ -1,6 +1,7 @@
 use core::marker::PhantomData;
 use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
 use core::slice;
 // use core::marker::{PhantomData, PhantomPinned};
 
@@ -30,10 +31,6 @@ impl<T: VectorElement> CxxVector<T> {
         T::__get_unchecked(self, pos)
     }
 
-    pub unsafe fn get_unchecked_mut(&mut self, pos: usize) -> &mut T {
-        T::__get_unchecked_mut(self, pos)
-    }
-
     pub fn get(&self, pos: usize) -> Option<&T> {
         if pos < self.len() {
             Some(unsafe { self.get_unchecked(pos) })
@@ -42,14 +39,6 @@ impl<T: VectorElement> CxxVector<T> {
         }
     }
 
-    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
-        if pos < self.len() {
-            Some(unsafe { self.get_unchecked_mut(pos) })
-        } else {
-            None
-        }
-    }
-
     /// Returns a slice to the underlying contiguous array of elements.
     pub fn as_slice(&self) -> &[T] {
         let len = self.vector_len();
@@ -68,16 +57,16 @@ impl<T: VectorElement> CxxVector<T> {
             }
         }
     }
+}
 
-    pub fn as_mut_slice(&mut self) -> &mut [T] {
-        let len = self.len();
-        if len == 0 {
-            &mut []
+impl<T: VectorElement> CxxVector<T> {
+    /// Pinned element, the only mutable access to `!Unpin` elements like
+    /// `CxxPath` or `CxxString`.
+    pub fn get_pin_mut(&mut self, pos: usize) -> Option<Pin<&mut T>> {
+        if pos < self.len() {
+            Some(unsafe { Pin::new_unchecked(T::__get_unchecked_mut(self, pos)) })
         } else {
-            unsafe {
-                let d = self.get_unchecked_mut(0);
-                slice::from_raw_parts_mut(d as *mut T, len)
-            }
+            None
         }
     }
 
@@ -88,7 +77,35 @@ impl<T: VectorElement> CxxVector<T> {
             T::__push_back(self, &mut value);
         }
     }
+}
+
+// elements like `CxxPath` can't be moved or swapped by Rust
+impl<T: VectorElement + Unpin> CxxVector<T> {
+    pub unsafe fn get_unchecked_mut(&mut self, pos: usize) -> &mut T {
+        T::__get_unchecked_mut(self, pos)
+    }
+
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        if pos < self.len() {
+            Some(unsafe { self.get_unchecked_mut(pos) })
+        } else {
+            None
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        if len == 0 {
+            &mut []
+        } else {
+            unsafe {
+                let d = self.get_unchecked_mut(0);
+                slice::from_raw_parts_mut(d as *mut T, len)
+            }
+        }
+    }
 
+    /// Moves the last element out to Rust.
     pub fn pop(&mut self) -> Option<T> {
         if self.is_empty() {
             None
@@ -109,7 +126,7 @@ impl<T: VectorElement> Deref for CxxVector<T> {
     }
 }
 
-impl<T: VectorElement> DerefMut for CxxVector<T> {
+impl<T: VectorElement + Unpin> DerefMut for CxxVector<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_mut_slice()
     }
@@ -1,17 +1,94 @@
 #[macro_export]
 macro_rules! genrs_fn {
+    // trailing `std::error_code &` argument, these must come first since a
+    // `ty` fragment can't be matched against `Result<..>` any more
+    ($vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? ($($arg:ident : $arg_type:ty),*) -> Result<(), CxxErrorCode>, ln=$link_name:ident) => {
+        $vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> Result<(), $crate::CxxErrorCode> {
+            extern "C" {
+                #[link_name = stringify!($link_name)]
+                fn __func $(<$($f_gt),+>)? ($($arg: $arg_type,)* __ec: &mut $crate::CxxErrorCode);
+            }
+            let mut __ec = $crate::CxxErrorCode::default();
+            unsafe {
+                __func($($arg,)* &mut __ec);
+            }
+            $crate::__resume_callback_panic();
+            if __ec.is_err() {
+                Err(__ec)
+            } else {
+                Ok(())
+            }
+        }
+    };
+
+    ($vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? ($($arg:ident : $arg_type:ty),*) -> Result<$ret_type:ty, CxxErrorCode>, cret=object, ln=$link_name:ident) => {
+        $vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> Result<$ret_type, $crate::CxxErrorCode> {
+            extern "C" {
+                #[link_name = stringify!($link_name)]
+                fn __func $(<$($f_gt),+>)? ($($arg: $arg_type,)* __ec: &mut $crate::CxxErrorCode, __ret: *mut $ret_type);
+            }
+            let mut __ec = $crate::CxxErrorCode::default();
+            let __ret = unsafe {
+                let mut __ret = std::mem::MaybeUninit::<$ret_type>::uninit();
+                __func($($arg,)* &mut __ec, __ret.as_mut_ptr());
+                __ret.assume_init()
+            };
+            $crate::__resume_callback_panic();
+            if __ec.is_err() {
+                Err(__ec)
+            } else {
+                Ok(__ret)
+            }
+        }
+    };
+
+    ($vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? ($($arg:ident : $arg_type:ty),*) -> Result<$ret_type:ty, CxxErrorCode>, cret=atomic, ln=$link_name:ident) => {
+        $vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> Result<$ret_type, $crate::CxxErrorCode> {
+            extern "C" {
+                #[link_name = stringify!($link_name)]
+                fn __func $(<$($f_gt),+>)? ($($arg: $arg_type,)* __ec: &mut $crate::CxxErrorCode) -> $ret_type;
+            }
+            let mut __ec = $crate::CxxErrorCode::default();
+            let __ret = unsafe { __func($($arg,)* &mut __ec) };
+            $crate::__resume_callback_panic();
+            if __ec.is_err() {
+                Err(__ec)
+            } else {
+                Ok(__ret)
+            }
+        }
+    };
+
+    ($vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? ($($arg:ident : $arg_type:ty),*) -> Result<(), CxxErrorCode>) => {
+        genrs_fn!($vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> Result<(), CxxErrorCode>, ln=$fn);
+    };
+
+    ($vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? ($($arg:ident : $arg_type:ty),*) -> Result<$ret_type:ty, CxxErrorCode>, cret=$c_ret_type:ident) => {
+        genrs_fn!($vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> Result<$ret_type, CxxErrorCode>, cret=$c_ret_type, ln=$fn);
+    };
+
+    ($vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? ($($arg:ident : $arg_type:ty),*) -> Result<$ret_type:ty, CxxErrorCode>, ln=$link_name:ident) => {
+        genrs_fn!($vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> Result<$ret_type, CxxErrorCode>, cret=object, ln=$link_name);
+    };
+
+    ($vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? ($($arg:ident : $arg_type:ty),*) -> Result<$ret_type:ty, CxxErrorCode>) => {
+        genrs_fn!($vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> Result<$ret_type, CxxErrorCode>, cret=object, ln=$fn);
+    };
+
     ($vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? ($($arg:ident : $arg_type:ty),*) -> $ret_type:ty, cret=object, ln=$link_name:ident) => {
         $vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> $ret_type {
             extern "C" {
                 #[link_name = stringify!($link_name)]
                 fn __func $(<$($f_gt),+>)? ($($arg: $arg_type),*, __ret: *mut $ret_type);
             }
-            unsafe {
+            let __ret = unsafe {
                 let mut __ret = std::mem::MaybeUninit::<$ret_type>::uninit();
                 let mut __ret_ptr = __ret.as_mut_ptr();
                 __func($($arg),*, __ret_ptr);
                 __ret.assume_init()
-            }
+            };
+            $crate::__resume_callback_panic();
+            __ret
         }
     };
 
@@ -21,9 +98,11 @@ macro_rules! genrs_fn {
                 #[link_name = stringify!($link_name)]
                 fn __func $(<$($f_gt),+>)? ($($arg: $arg_type),*) -> $ret_type;
             }
-            unsafe {
+            let __ret = unsafe {
                 __func($($arg),*)
-            }
+            };
+            $crate::__resume_callback_panic();
+            __ret
         }
     };
 
@@ -46,8 +125,9 @@ macro_rules! genrs_fn {
                 fn __func $(<$($f_gt),+>)? ($($arg: $arg_type),*);
             }
             unsafe {
-                __func($($arg),*)
+                __func($($arg),*);
             }
+            $crate::__resume_callback_panic();
         }
     };
 
@@ -55,6 +135,166 @@ macro_rules! genrs_fn {
         genrs_fn!($vis fn $fn $(<$($f_gt),+>)? ($($arg: $arg_type),*), ln=$fn);
     };
 
+    // &self with a trailing `std::error_code &` argument
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&self $(, $arg:ident : $arg_type:ty)*) -> Result<(), CxxErrorCode>, ln=$link_name:ident) => {
+        impl $(<$($c_gt),+>)? $cls {
+            $vis fn $fn $(<$($f_gt),+>)? (&self $(, $arg: $arg_type)*) -> Result<(), $crate::CxxErrorCode> {
+                extern "C" {
+                    #[link_name = stringify!($link_name)]
+                    fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this $cls $(, $arg: $arg_type)*, __ec: &mut $crate::CxxErrorCode);
+                }
+                let mut __ec = $crate::CxxErrorCode::default();
+                unsafe {
+                    __func(self $(, $arg)*, &mut __ec);
+                }
+                $crate::__resume_callback_panic();
+                if __ec.is_err() {
+                    Err(__ec)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>, cret=object, ln=$link_name:ident) => {
+        impl $(<$($c_gt),+>)? $cls {
+            $vis fn $fn $(<$($f_gt),+>)? (&self $(, $arg: $arg_type)*) -> Result<$ret_type, $crate::CxxErrorCode> {
+                extern "C" {
+                    #[link_name = stringify!($link_name)]
+                    fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this $cls $(, $arg: $arg_type)*, __ec: &mut $crate::CxxErrorCode, __ret: *mut $ret_type);
+                }
+                let mut __ec = $crate::CxxErrorCode::default();
+                let __ret = unsafe {
+                    let mut __ret = std::mem::MaybeUninit::<$ret_type>::uninit();
+                    __func(self $(, $arg)*, &mut __ec, __ret.as_mut_ptr());
+                    __ret.assume_init()
+                };
+                $crate::__resume_callback_panic();
+                if __ec.is_err() {
+                    Err(__ec)
+                } else {
+                    Ok(__ret)
+                }
+            }
+        }
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>, cret=atomic, ln=$link_name:ident) => {
+        impl $(<$($c_gt),+>)? $cls {
+            $vis fn $fn $(<$($f_gt),+>)? (&self $(, $arg: $arg_type)*) -> Result<$ret_type, $crate::CxxErrorCode> {
+                extern "C" {
+                    #[link_name = stringify!($link_name)]
+                    fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this $cls $(, $arg: $arg_type)*, __ec: &mut $crate::CxxErrorCode) -> $ret_type;
+                }
+                let mut __ec = $crate::CxxErrorCode::default();
+                let __ret = unsafe { __func(self $(, $arg)*, &mut __ec) };
+                $crate::__resume_callback_panic();
+                if __ec.is_err() {
+                    Err(__ec)
+                } else {
+                    Ok(__ret)
+                }
+            }
+        }
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&self $(, $arg:ident : $arg_type:ty)*) -> Result<(), CxxErrorCode>) => {
+        genrs_fn!($cls; $(impl<$($c_gt),+>)?; $vis fn $fn $(<$($f_gt),+>)? (&self $(, $arg : $arg_type)*) -> Result<(), CxxErrorCode>, ln=$fn);
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>, cret=$c_ret_type:ident) => {
+        genrs_fn!($cls; $(impl<$($c_gt),+>)?; $vis fn $fn $(<$($f_gt),+>)? (&self $(, $arg : $arg_type)*) -> Result<$ret_type, CxxErrorCode>, cret=$c_ret_type, ln=$fn);
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>, ln=$link_name:ident) => {
+        genrs_fn!($cls; $(impl<$($c_gt),+>)?; $vis fn $fn $(<$($f_gt),+>)? (&self $(, $arg : $arg_type)*) -> Result<$ret_type, CxxErrorCode>, cret=object, ln=$link_name);
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>) => {
+        genrs_fn!($cls; $(impl<$($c_gt),+>)?; $vis fn $fn $(<$($f_gt),+>)? (&self $(, $arg : $arg_type)*) -> Result<$ret_type, CxxErrorCode>, cret=object, ln=$fn);
+    };
+
+    // &mut self with a trailing `std::error_code &` argument
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&mut self $(, $arg:ident : $arg_type:ty)*) -> Result<(), CxxErrorCode>, ln=$link_name:ident) => {
+        impl $(<$($c_gt),+>)? $cls {
+            $vis fn $fn $(<$($f_gt),+>)? (&mut self $(, $arg: $arg_type)*) -> Result<(), $crate::CxxErrorCode> {
+                extern "C" {
+                    #[link_name = stringify!($link_name)]
+                    fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this mut $cls $(, $arg: $arg_type)*, __ec: &mut $crate::CxxErrorCode);
+                }
+                let mut __ec = $crate::CxxErrorCode::default();
+                unsafe {
+                    __func(self $(, $arg)*, &mut __ec);
+                }
+                $crate::__resume_callback_panic();
+                if __ec.is_err() {
+                    Err(__ec)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&mut self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>, cret=object, ln=$link_name:ident) => {
+        impl $(<$($c_gt),+>)? $cls {
+            $vis fn $fn $(<$($f_gt),+>)? (&mut self $(, $arg: $arg_type)*) -> Result<$ret_type, $crate::CxxErrorCode> {
+                extern "C" {
+                    #[link_name = stringify!($link_name)]
+                    fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this mut $cls $(, $arg: $arg_type)*, __ec: &mut $crate::CxxErrorCode, __ret: *mut $ret_type);
+                }
+                let mut __ec = $crate::CxxErrorCode::default();
+                let __ret = unsafe {
+                    let mut __ret = std::mem::MaybeUninit::<$ret_type>::uninit();
+                    __func(self $(, $arg)*, &mut __ec, __ret.as_mut_ptr());
+                    __ret.assume_init()
+                };
+                $crate::__resume_callback_panic();
+                if __ec.is_err() {
+                    Err(__ec)
+                } else {
+                    Ok(__ret)
+                }
+            }
+        }
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&mut self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>, cret=atomic, ln=$link_name:ident) => {
+        impl $(<$($c_gt),+>)? $cls {
+            $vis fn $fn $(<$($f_gt),+>)? (&mut self $(, $arg: $arg_type)*) -> Result<$ret_type, $crate::CxxErrorCode> {
+                extern "C" {
+                    #[link_name = stringify!($link_name)]
+                    fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this mut $cls $(, $arg: $arg_type)*, __ec: &mut $crate::CxxErrorCode) -> $ret_type;
+                }
+                let mut __ec = $crate::CxxErrorCode::default();
+                let __ret = unsafe { __func(self $(, $arg)*, &mut __ec) };
+                $crate::__resume_callback_panic();
+                if __ec.is_err() {
+                    Err(__ec)
+                } else {
+                    Ok(__ret)
+                }
+            }
+        }
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&mut self $(, $arg:ident : $arg_type:ty)*) -> Result<(), CxxErrorCode>) => {
+        genrs_fn!($cls; $(impl<$($c_gt),+>)?; $vis fn $fn $(<$($f_gt),+>)? (&mut self $(, $arg : $arg_type)*) -> Result<(), CxxErrorCode>, ln=$fn);
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&mut self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>, cret=$c_ret_type:ident) => {
+        genrs_fn!($cls; $(impl<$($c_gt),+>)?; $vis fn $fn $(<$($f_gt),+>)? (&mut self $(, $arg : $arg_type)*) -> Result<$ret_type, CxxErrorCode>, cret=$c_ret_type, ln=$fn);
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&mut self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>, ln=$link_name:ident) => {
+        genrs_fn!($cls; $(impl<$($c_gt),+>)?; $vis fn $fn $(<$($f_gt),+>)? (&mut self $(, $arg : $arg_type)*) -> Result<$ret_type, CxxErrorCode>, cret=object, ln=$link_name);
+    };
+
+    ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&mut self $(, $arg:ident : $arg_type:ty)*) -> Result<$ret_type:ty, CxxErrorCode>) => {
+        genrs_fn!($cls; $(impl<$($c_gt),+>)?; $vis fn $fn $(<$($f_gt),+>)? (&mut self $(, $arg : $arg_type)*) -> Result<$ret_type, CxxErrorCode>, cret=object, ln=$fn);
+    };
+
     // &self ret
     ($cls:ty; $(impl<$($c_gt:tt),+>)?; $vis:vis fn $fn:ident $(<$($f_gt:tt),+>)? (&self $(, $arg:ident : $arg_type:ty)*) -> $ret_type:ty, cret=object, ln=$link_name:ident) => {
         impl $(<$($c_gt),+>)? $cls {
@@ -63,12 +303,14 @@ macro_rules! genrs_fn {
                     #[link_name = stringify!($link_name)]
                     fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)? > (this: &'this $cls $(, $arg: $arg_type)*, __ret: *mut $ret_type);
                 }
-                unsafe {
+                let __ret = unsafe {
                     let mut __ret = std::mem::MaybeUninit::<$ret_type>::uninit();
                     let mut __ret_ptr = __ret.as_mut_ptr();
                     __func(self $(, $arg)*, __ret_ptr);
                     __ret.assume_init()
-                }
+                };
+                $crate::__resume_callback_panic();
+                __ret
             }
         }
     };
@@ -80,9 +322,11 @@ macro_rules! genrs_fn {
                     #[link_name = stringify!($link_name)]
                     fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this $cls $(, $arg: $arg_type)*) -> $ret_type;
                 }
-                unsafe {
+                let __ret = unsafe {
                     __func(self $(, $arg)*)
-                }
+                };
+                $crate::__resume_callback_panic();
+                __ret
             }
         }
     };
@@ -108,8 +352,9 @@ macro_rules! genrs_fn {
                     fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this $cls $(, $arg: $arg_type)*);
                 }
                 unsafe {
-                    __func(self $(, $arg)*)
+                    __func(self $(, $arg)*);
                 }
+                $crate::__resume_callback_panic();
             }
         }
     };
@@ -126,12 +371,14 @@ macro_rules! genrs_fn {
                     #[link_name = stringify!($link_name)]
                     fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this mut $cls $(, $arg: $arg_type)*, __ret: *mut $ret_type);
                 }
-                unsafe {
+                let __ret = unsafe {
                     let mut __ret = std::mem::MaybeUninit::<$ret_type>::uninit();
                     let mut __ret_ptr = __ret.as_mut_ptr();
                     __func(self $(, $arg)*, __ret_ptr);
                     __ret.assume_init()
-                }
+                };
+                $crate::__resume_callback_panic();
+                __ret
             }
         }
     };
@@ -143,9 +390,11 @@ macro_rules! genrs_fn {
                     #[link_name = stringify!($link_name)]
                     fn __func <'this $(,$($c_gt),+)? $(,($f_gt),+)?> (this: &'this mut $cls $(, $arg: $arg_type)*) -> $ret_type;
                 }
-                unsafe {
+                let __ret = unsafe {
                     __func(self $(, $arg)*)
-                }
+                };
+                $crate::__resume_callback_panic();
+                __ret
             }
         }
     };
@@ -173,6 +422,7 @@ macro_rules! genrs_fn {
                 unsafe {
                     __func(self $(, $arg)*);
                 }
+                $crate::__resume_callback_panic();
             }
         }
     };
@@ -224,6 +474,35 @@ macro_rules! genrs_shared_ptr {
     };
 }
 
+/// Implements `MakeShared` with the shims of `genc_make_shared`, spelled
+/// `genrs_make_shared!(unsafe rxx_shared_i64, i64)`. Only for types whose
+/// Rust definition has the C++ layout, an opaque placeholder type would be
+/// moved into C++ by `SharedPtr::new`.
+#[macro_export]
+macro_rules! genrs_make_shared {
+    (unsafe $link_name:ident, $tp:ty) => {
+        paste::paste! {
+            unsafe impl $crate::MakeShared for $tp {
+            unsafe fn __new(value: *mut core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _new>])]
+                fn func(value: *mut core::ffi::c_void, out: *mut core::ffi::c_void);
+                }
+                func(value, out);
+            }
+
+            unsafe fn __new_default(out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _new_default>])]
+                fn func(out: *mut core::ffi::c_void);
+                }
+                func(out);
+            }
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! genrs_weak_ptr {
     ($link_name:ident, $tp:ty) => {
@@ -266,63 +545,1264 @@ macro_rules! genrs_weak_ptr {
 }
 
 #[macro_export]
-macro_rules! genrs_vector {
+macro_rules! genrs_atomic_shared_ptr {
     ($link_name:ident, $tp:ty) => {
         paste::paste! {
-            impl $crate::VectorElement for $tp {
-                unsafe fn __drop(this: &mut $crate::CxxVector<$tp>) {
-                    extern "C" {
-                        #[link_name=stringify!([<$link_name _delete>])]
-                        fn func(this: &mut $crate::CxxVector<$tp>);
-                    }
-                    func(this)
+            impl $crate::AtomicSharedPtrTarget for $tp {
+            unsafe fn __load(this: *const core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _atomic_load>])]
+                fn func(this: *const core::ffi::c_void, out: *mut core::ffi::c_void);
                 }
+                func(this, out);
+            }
 
-                unsafe fn __size(this: &$crate::CxxVector<$tp>) -> usize {
-                    extern "C" {
-                        #[link_name=stringify!([<$link_name _size>])]
-                        fn func(this: &$crate::CxxVector<$tp>) -> usize;
-                    }
-                    func(this)
+            unsafe fn __store(this: *mut core::ffi::c_void, value: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _atomic_store>])]
+                fn func(this: *mut core::ffi::c_void, value: *mut core::ffi::c_void);
                 }
+                func(this, value);
+            }
 
-                unsafe fn __get_unchecked(this: &$crate::CxxVector<$tp>, pos: usize) -> &Self {
-                    extern "C" {
-                        #[link_name=stringify!([<$link_name _get>])]
-                        fn func(this: &$crate::CxxVector<$tp>, pos: usize) -> &$tp;
-                    }
-                    func(this, pos)
+            unsafe fn __exchange(this: *mut core::ffi::c_void, value: *mut core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _atomic_exchange>])]
+                fn func(this: *mut core::ffi::c_void, value: *mut core::ffi::c_void, out: *mut core::ffi::c_void);
                 }
+                func(this, value, out);
+            }
 
-                unsafe fn __get_unchecked_mut(this: &mut $crate::CxxVector<$tp>, pos: usize) -> &mut Self {
-                    extern "C" {
-                        #[link_name=stringify!([<$link_name _get_mut>])]
-                        fn func(this: &mut $crate::CxxVector<$tp>, pos: usize) -> &mut $tp;
-                    }
-                    func(this, pos)
+            unsafe fn __compare_exchange(this: *mut core::ffi::c_void, expected: *mut core::ffi::c_void, desired: *const core::ffi::c_void) -> bool {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _atomic_compare_exchange>])]
+                fn func(this: *mut core::ffi::c_void, expected: *mut core::ffi::c_void, desired: *const core::ffi::c_void) -> bool;
                 }
+                func(this, expected, desired)
+            }
+            }
+        }
+    };
+}
 
-                unsafe fn __push_back(this: &mut $crate::CxxVector<$tp>, value: &mut $tp) {
-                    extern "C" {
-                        #[link_name=stringify!([<$link_name _push_back>])]
-                        fn func(this: &mut $crate::CxxVector<$tp>, value: &mut $tp);
-                    }
-                    func(this, value)
+#[macro_export]
+macro_rules! genrs_pointer_cast {
+    ($link_name:ident, $base:ty, $derived:ty) => {
+        paste::paste! {
+            impl $crate::DerivedFrom<$base> for $derived {
+            unsafe fn __shared_downcast(this: *const core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _shared_downcast>])]
+                fn func(this: *const core::ffi::c_void, out: *mut core::ffi::c_void);
+                }
+                func(this, out);
+            }
+
+            unsafe fn __shared_static_downcast(this: *const core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _shared_static_downcast>])]
+                fn func(this: *const core::ffi::c_void, out: *mut core::ffi::c_void);
                 }
+                func(this, out);
+            }
 
-                unsafe fn __pop_back(this: &mut $crate::CxxVector<$tp>, value: *mut $tp) {
-                    extern "C" {
-                        #[link_name=stringify!([<$link_name _pop_back>])]
-                        fn func(this: &mut $crate::CxxVector<$tp>, value: *mut $tp);
-                    }
-                    func(this, value)
+            unsafe fn __shared_upcast(this: *const core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _shared_upcast>])]
+                fn func(this: *const core::ffi::c_void, out: *mut core::ffi::c_void);
+                }
+                func(this, out);
+            }
+
+            unsafe fn __unique_downcast(this: *mut core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _unique_downcast>])]
+                fn func(this: *mut core::ffi::c_void, out: *mut core::ffi::c_void);
+                }
+                func(this, out);
+            }
+
+            unsafe fn __unique_static_downcast(this: *mut core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _unique_static_downcast>])]
+                fn func(this: *mut core::ffi::c_void, out: *mut core::ffi::c_void);
                 }
+                func(this, out);
+            }
 
+            unsafe fn __unique_upcast(this: *mut core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _unique_upcast>])]
+                fn func(this: *mut core::ffi::c_void, out: *mut core::ffi::c_void);
+                }
+                func(this, out);
+            }
             }
         }
-    }
+    };
 }
 
-genrs_unique_ptr!(rxx_unique_string, crate::CxxString);
-genrs_shared_ptr!(rxx_shared_string, crate::CxxString);
-genrs_weak_ptr!(rxx_weak_string, crate::CxxString);
+#[macro_export]
+macro_rules! genrs_shared_from_this {
+    ($link_name:ident, $tp:ty) => {
+        paste::paste! {
+            impl $crate::SharedFromThis for $tp {
+            unsafe fn __shared_from_this(this: *const core::ffi::c_void, out: *mut core::ffi::c_void) -> bool {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _shared_from_this>])]
+                fn func(this: *const core::ffi::c_void, out: *mut core::ffi::c_void) -> bool;
+                }
+                func(this, out)
+            }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_rust_type {
+    ($link_name:ident, $tp:ty) => {
+        paste::paste! {
+            #[allow(non_snake_case)]
+            #[export_name=stringify!([<$link_name _drop>])]
+            unsafe extern "C" fn [<__ $link_name _drop>](this: *mut $tp) {
+                drop(Box::from_raw(this));
+            }
+
+            unsafe impl $crate::RustType for $tp {}
+
+            impl $crate::UniquePtrTarget for $tp {
+            unsafe fn __drop(this: *mut core::ffi::c_void) {
+                let ptr = *(this as *mut *mut $tp);
+                if !ptr.is_null() {
+                [<__ $link_name _drop>](ptr);
+                }
+            }
+            }
+
+            impl $crate::SharedPtrTarget for $tp {
+            unsafe fn __drop(this: *mut core::ffi::c_void) {
+                extern "C" {
+                fn rxx_shared_ptr_void_delete(this: *mut core::ffi::c_void);
+                }
+                rxx_shared_ptr_void_delete(this);
+            }
+
+            unsafe fn __clone(this: *const core::ffi::c_void, out: *mut core::ffi::c_void) {
+                extern "C" {
+                fn rxx_shared_ptr_void_clone(this: *const core::ffi::c_void, out: *mut core::ffi::c_void);
+                }
+                rxx_shared_ptr_void_clone(this, out);
+            }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_type_info {
+    ($link_name:ident, $tp:ty) => {
+        paste::paste! {
+            impl $crate::TypeInfoTarget for $tp {
+            unsafe fn __type_info() -> &'static $crate::CxxTypeInfo {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _type_info>])]
+                fn func() -> &'static $crate::CxxTypeInfo;
+                }
+                func()
+            }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_any {
+    ($link_name:ident, $tp:ty) => {
+        $crate::genrs_type_info!($link_name, $tp);
+
+        paste::paste! {
+            impl $crate::AnyElement for $tp {
+            unsafe fn __any_new(value: &mut $tp, out: *mut $crate::CxxAny) {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _any_new>])]
+                fn func(value: &mut $tp, out: *mut $crate::CxxAny);
+                }
+                func(value, out);
+            }
+
+            unsafe fn __any_cast(this: &$crate::CxxAny) -> *const $tp {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _any_cast>])]
+                fn func(this: &$crate::CxxAny) -> *const $tp;
+                }
+                func(this)
+            }
+
+            unsafe fn __any_cast_mut(this: &mut $crate::CxxAny) -> *mut $tp {
+                extern "C" {
+                #[link_name=stringify!([<$link_name _any_cast_mut>])]
+                fn func(this: &mut $crate::CxxAny) -> *mut $tp;
+                }
+                func(this)
+            }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_optional {
+    ($link_name:ident, $tp:ty) => {
+        paste::paste! {
+            impl $crate::OptionalElement for $tp {
+                unsafe fn __drop(this: &mut $crate::CxxOptional<$tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _delete>])]
+                        fn func(this: &mut $crate::CxxOptional<$tp>);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __new_none(out: *mut $crate::CxxOptional<$tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new_none>])]
+                        fn func(out: *mut $crate::CxxOptional<$tp>);
+                    }
+                    func(out)
+                }
+
+                unsafe fn __new(value: &mut $tp, out: *mut $crate::CxxOptional<$tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new>])]
+                        fn func(value: &mut $tp, out: *mut $crate::CxxOptional<$tp>);
+                    }
+                    func(value, out)
+                }
+
+                unsafe fn __get(this: &$crate::CxxOptional<$tp>) -> *const $tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get>])]
+                        fn func(this: &$crate::CxxOptional<$tp>) -> *const $tp;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __get_mut(this: &mut $crate::CxxOptional<$tp>) -> *mut $tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get_mut>])]
+                        fn func(this: &mut $crate::CxxOptional<$tp>) -> *mut $tp;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __take(this: &mut $crate::CxxOptional<$tp>, out: *mut $tp) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _take>])]
+                        fn func(this: &mut $crate::CxxOptional<$tp>, out: *mut $tp) -> bool;
+                    }
+                    func(this, out)
+                }
+            }
+        }
+    };
+}
+
+/// Declares the Rust enum `$name`, its borrowed view `<$name>Ref` and binds
+/// them to the variant generated by `genc_variant` with the same alternatives.
+#[macro_export]
+macro_rules! genrs_variant {
+    ($link_name:ident, $(#[$meta:meta])* $vis:vis enum $name:ident { $($variant:ident($tp:ty)),+ $(,)? }) => {
+        paste::paste! {
+            $(#[$meta])*
+            $vis enum $name {
+                $($variant($tp)),+
+            }
+
+            #[derive(Clone, Copy)]
+            $vis enum [<$name Ref>]<'a> {
+                $($variant(&'a $tp)),+
+            }
+
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            #[repr(C)]
+            $vis union [<$name Storage>] {
+                $($variant: core::mem::ManuallyDrop<$tp>),+
+            }
+
+            impl $crate::VariantEnum for $name {
+                type Storage = [<$name Storage>];
+                type Ref<'a> = [<$name Ref>]<'a> where Self: 'a;
+
+                unsafe fn __drop(this: &mut $crate::CxxVariant<$name>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _delete>])]
+                        fn func(this: &mut $crate::CxxVariant<$name>);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __index(this: &$crate::CxxVariant<$name>) -> usize {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _index>])]
+                        fn func(this: &$crate::CxxVariant<$name>) -> usize;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __as_enum(this: &$crate::CxxVariant<$name>) -> [<$name Ref>]<'_> {
+                    $(
+                        {
+                            extern "C" {
+                                #[link_name=stringify!([<$link_name _get_if_ $variant>])]
+                                fn func(this: &$crate::CxxVariant<$name>) -> *const $tp;
+                            }
+                            if let Some(value) = func(this).as_ref() {
+                                return [<$name Ref>]::$variant(value);
+                            }
+                        }
+                    )+
+                    unreachable!()
+                }
+
+                unsafe fn __new(value: $name, out: *mut $crate::CxxVariant<$name>) {
+                    // C++ calls move constructor followed by destructor on `value`
+                    match value {
+                        $(
+                            $name::$variant(value) => {
+                                extern "C" {
+                                    #[link_name=stringify!([<$link_name _new_ $variant>])]
+                                    fn func(value: &mut $tp, out: *mut $crate::CxxVariant<$name>);
+                                }
+                                func(&mut core::mem::ManuallyDrop::new(value), out)
+                            }
+                        )+
+                    }
+                }
+
+                unsafe fn __emplace(this: &mut $crate::CxxVariant<$name>, value: $name) {
+                    match value {
+                        $(
+                            $name::$variant(value) => {
+                                extern "C" {
+                                    #[link_name=stringify!([<$link_name _emplace_ $variant>])]
+                                    fn func(this: &mut $crate::CxxVariant<$name>, value: &mut $tp);
+                                }
+                                func(this, &mut core::mem::ManuallyDrop::new(value))
+                            }
+                        )+
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Binds the Rust tuple `($tp, ...)` of up to 6 elements to the `std::tuple`
+/// generated by `genc_tuple` with the same element types.
+#[macro_export]
+macro_rules! genrs_tuple {
+    ($link_name:ident, ($a:ty, $b:ty $(,)?)) => {
+        $crate::genrs_tuple!(@impl $link_name, TupleLayout2, ($a, $b), (0 a $a), (1 b $b));
+    };
+    ($link_name:ident, ($a:ty, $b:ty, $c:ty $(,)?)) => {
+        $crate::genrs_tuple!(@impl $link_name, TupleLayout3, ($a, $b, $c), (0 a $a), (1 b $b), (2 c $c));
+    };
+    ($link_name:ident, ($a:ty, $b:ty, $c:ty, $d:ty $(,)?)) => {
+        $crate::genrs_tuple!(@impl $link_name, TupleLayout4, ($a, $b, $c, $d), (0 a $a), (1 b $b), (2 c $c), (3 d $d));
+    };
+    ($link_name:ident, ($a:ty, $b:ty, $c:ty, $d:ty, $e:ty $(,)?)) => {
+        $crate::genrs_tuple!(@impl $link_name, TupleLayout5, ($a, $b, $c, $d, $e), (0 a $a), (1 b $b), (2 c $c), (3 d $d), (4 e $e));
+    };
+    ($link_name:ident, ($a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty $(,)?)) => {
+        $crate::genrs_tuple!(@impl $link_name, TupleLayout6, ($a, $b, $c, $d, $e, $f), (0 a $a), (1 b $b), (2 c $c), (3 d $d), (4 e $e), (5 f $f));
+    };
+
+    (@impl $link_name:ident, $layout:ident, $tuple:ty, $(($idx:tt $var:ident $tp:ty)),+) => {
+        paste::paste! {
+            impl $crate::TupleElements for $tuple {
+                type Layout = $crate::$layout<$($tp),+>;
+                type Refs<'a> = ($(&'a $tp),+) where Self: 'a;
+                type MutRefs<'a> = ($(&'a mut $tp),+) where Self: 'a;
+
+                unsafe fn __drop(this: &mut $crate::CxxTuple<$tuple>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _delete>])]
+                        fn func(this: &mut $crate::CxxTuple<$tuple>);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __new(value: $tuple, out: *mut $crate::CxxTuple<$tuple>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new>])]
+                        fn func($($var: &mut $tp),+, out: *mut $crate::CxxTuple<$tuple>);
+                    }
+                    // C++ calls move constructor followed by destructor on each element
+                    let ($($var),+) = value;
+                    func($(&mut core::mem::ManuallyDrop::new($var)),+, out)
+                }
+
+                unsafe fn __take(this: &mut $crate::CxxTuple<$tuple>, out: *mut $tuple) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _take>])]
+                        fn func(this: &mut $crate::CxxTuple<$tuple>, $($var: *mut $tp),+);
+                    }
+                    func(this, $(core::ptr::addr_of_mut!((*out).$idx)),+)
+                }
+
+                unsafe fn __as_refs(this: &$crate::CxxTuple<$tuple>) -> ($(&$tp),+) {
+                    ($({
+                        extern "C" {
+                            #[link_name=stringify!([<$link_name _get_ $idx>])]
+                            fn func(this: &$crate::CxxTuple<$tuple>) -> &$tp;
+                        }
+                        func(this)
+                    }),+)
+                }
+
+                unsafe fn __as_mut_refs(this: &mut $crate::CxxTuple<$tuple>) -> ($(&mut $tp),+) {
+                    let this = this as *mut $crate::CxxTuple<$tuple>;
+                    ($({
+                        extern "C" {
+                            #[link_name=stringify!([<$link_name _get_mut_ $idx>])]
+                            fn func(this: *mut $crate::CxxTuple<$tuple>) -> *mut $tp;
+                        }
+                        &mut *func(this)
+                    }),+)
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_unordered_map {
+    ($link_name:ident, $key_tp:ty, $value_tp:ty) => {
+        paste::paste! {
+            impl $crate::UnorderedMapElement<$value_tp> for $key_tp {
+                unsafe fn __unique_drop(this: *mut core::ffi::c_void) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _unique_delete>])]
+                        fn func(this: *mut core::ffi::c_void);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __new(out: *mut $crate::UniquePtr<$crate::CxxUnorderedMap<$key_tp, $value_tp>>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new>])]
+                        fn func(out: *mut $crate::UniquePtr<$crate::CxxUnorderedMap<$key_tp, $value_tp>>);
+                    }
+                    func(out)
+                }
+
+                unsafe fn __len(this: &$crate::CxxUnorderedMap<$key_tp, $value_tp>) -> usize {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _size>])]
+                        fn func(this: &$crate::CxxUnorderedMap<$key_tp, $value_tp>) -> usize;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __get(this: &$crate::CxxUnorderedMap<$key_tp, $value_tp>, key: &$key_tp) -> *const $value_tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get>])]
+                        fn func(this: &$crate::CxxUnorderedMap<$key_tp, $value_tp>, key: &$key_tp) -> *const $value_tp;
+                    }
+                    func(this, key)
+                }
+
+                unsafe fn __get_mut(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>, key: &$key_tp) -> *mut $value_tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get_mut>])]
+                        fn func(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>, key: &$key_tp) -> *mut $value_tp;
+                    }
+                    func(this, key)
+                }
+
+                unsafe fn __insert(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>, key: &$key_tp, value: &mut $value_tp, old: *mut $value_tp) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _insert>])]
+                        fn func(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>, key: &$key_tp, value: &mut $value_tp, old: *mut $value_tp) -> bool;
+                    }
+                    func(this, key, value, old)
+                }
+
+                unsafe fn __remove(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>, key: &$key_tp, out: *mut $value_tp) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _remove>])]
+                        fn func(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>, key: &$key_tp, out: *mut $value_tp) -> bool;
+                    }
+                    func(this, key, out)
+                }
+
+                unsafe fn __clear(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _clear>])]
+                        fn func(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __reserve(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>, n: usize) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _reserve>])]
+                        fn func(this: &mut $crate::CxxUnorderedMap<$key_tp, $value_tp>, n: usize);
+                    }
+                    func(this, n)
+                }
+
+                unsafe fn __iter_new(this: &$crate::CxxUnorderedMap<$key_tp, $value_tp>, it: *mut $crate::RawIter) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _iter_new>])]
+                        fn func(this: &$crate::CxxUnorderedMap<$key_tp, $value_tp>, it: *mut $crate::RawIter);
+                    }
+                    func(this, it)
+                }
+
+                unsafe fn __iter_next(this: &$crate::CxxUnorderedMap<$key_tp, $value_tp>, it: &mut $crate::RawIter, key: &mut *const $key_tp) -> *const $value_tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _iter_next>])]
+                        fn func(this: &$crate::CxxUnorderedMap<$key_tp, $value_tp>, it: &mut $crate::RawIter, key: &mut *const $key_tp) -> *const $value_tp;
+                    }
+                    func(this, it, key)
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! genrs_ordered_map_shims {
+    ($link_name:ident, $key_tp:ty, $value_tp:ty, $multi:literal) => {
+        paste::paste! {
+            impl $crate::OrderedMapShims<$value_tp, $multi> for $key_tp {
+                unsafe fn __unique_drop(this: *mut core::ffi::c_void) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _unique_delete>])]
+                        fn func(this: *mut core::ffi::c_void);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __new(out: *mut core::ffi::c_void) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new>])]
+                        fn func(out: *mut core::ffi::c_void);
+                    }
+                    func(out)
+                }
+
+                unsafe fn __len(this: *const core::ffi::c_void) -> usize {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _size>])]
+                        fn func(this: *const core::ffi::c_void) -> usize;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __get(this: *const core::ffi::c_void, key: &$key_tp) -> *const $value_tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get>])]
+                        fn func(this: *const core::ffi::c_void, key: &$key_tp) -> *const $value_tp;
+                    }
+                    func(this, key)
+                }
+
+                unsafe fn __get_mut(this: *mut core::ffi::c_void, key: &$key_tp) -> *mut $value_tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get_mut>])]
+                        fn func(this: *mut core::ffi::c_void, key: &$key_tp) -> *mut $value_tp;
+                    }
+                    func(this, key)
+                }
+
+                unsafe fn __clear(this: *mut core::ffi::c_void) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _clear>])]
+                        fn func(this: *mut core::ffi::c_void);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __range(
+                    this: *const core::ffi::c_void,
+                    start_kind: u8,
+                    start: *const $key_tp,
+                    end_kind: u8,
+                    end: *const $key_tp,
+                    begin_it: *mut $crate::RawIter,
+                    end_it: *mut $crate::RawIter,
+                ) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _range>])]
+                        fn func(
+                            this: *const core::ffi::c_void,
+                            start_kind: u8,
+                            start: *const $key_tp,
+                            end_kind: u8,
+                            end: *const $key_tp,
+                            begin_it: *mut $crate::RawIter,
+                            end_it: *mut $crate::RawIter,
+                        );
+                    }
+                    func(this, start_kind, start, end_kind, end, begin_it, end_it)
+                }
+
+                unsafe fn __range_next(this: *const core::ffi::c_void, it: &mut $crate::RawIter, end: &$crate::RawIter, key: &mut *const $key_tp) -> *const $value_tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _range_next>])]
+                        fn func(this: *const core::ffi::c_void, it: &mut $crate::RawIter, end: &$crate::RawIter, key: &mut *const $key_tp) -> *const $value_tp;
+                    }
+                    func(this, it, end, key)
+                }
+
+                unsafe fn __last(this: *const core::ffi::c_void, key: &mut *const $key_tp) -> *const $value_tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _last>])]
+                        fn func(this: *const core::ffi::c_void, key: &mut *const $key_tp) -> *const $value_tp;
+                    }
+                    func(this, key)
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_map {
+    ($link_name:ident, $key_tp:ty, $value_tp:ty) => {
+        $crate::genrs_ordered_map_shims!($link_name, $key_tp, $value_tp, false);
+
+        paste::paste! {
+            impl $crate::MapElement<$value_tp> for $key_tp {
+                unsafe fn __insert(this: &mut $crate::CxxMap<$key_tp, $value_tp>, key: &$key_tp, value: &mut $value_tp, old: *mut $value_tp) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _insert>])]
+                        fn func(this: &mut $crate::CxxMap<$key_tp, $value_tp>, key: &$key_tp, value: &mut $value_tp, old: *mut $value_tp) -> bool;
+                    }
+                    func(this, key, value, old)
+                }
+
+                unsafe fn __remove(this: &mut $crate::CxxMap<$key_tp, $value_tp>, key: &$key_tp, out: *mut $value_tp) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _remove>])]
+                        fn func(this: &mut $crate::CxxMap<$key_tp, $value_tp>, key: &$key_tp, out: *mut $value_tp) -> bool;
+                    }
+                    func(this, key, out)
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_multimap {
+    ($link_name:ident, $key_tp:ty, $value_tp:ty) => {
+        $crate::genrs_ordered_map_shims!($link_name, $key_tp, $value_tp, true);
+
+        paste::paste! {
+            impl $crate::MultiMapElement<$value_tp> for $key_tp {
+                unsafe fn __insert(this: &mut $crate::CxxMultiMap<$key_tp, $value_tp>, key: &$key_tp, value: &mut $value_tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _insert>])]
+                        fn func(this: &mut $crate::CxxMultiMap<$key_tp, $value_tp>, key: &$key_tp, value: &mut $value_tp);
+                    }
+                    func(this, key, value)
+                }
+
+                unsafe fn __remove(this: &mut $crate::CxxMultiMap<$key_tp, $value_tp>, key: &$key_tp) -> usize {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _remove>])]
+                        fn func(this: &mut $crate::CxxMultiMap<$key_tp, $value_tp>, key: &$key_tp) -> usize;
+                    }
+                    func(this, key)
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! genrs_set_shims {
+    ($elem:ident, $set:ident, $link_name:ident, $tp:ty, { $($extra:tt)* }) => {
+        paste::paste! {
+            impl $crate::$elem for $tp {
+                unsafe fn __unique_drop(this: *mut core::ffi::c_void) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _unique_delete>])]
+                        fn func(this: *mut core::ffi::c_void);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __new(out: *mut $crate::UniquePtr<$crate::$set<$tp>>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new>])]
+                        fn func(out: *mut $crate::UniquePtr<$crate::$set<$tp>>);
+                    }
+                    func(out)
+                }
+
+                unsafe fn __len(this: &$crate::$set<$tp>) -> usize {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _size>])]
+                        fn func(this: &$crate::$set<$tp>) -> usize;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __contains(this: &$crate::$set<$tp>, value: &$tp) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _contains>])]
+                        fn func(this: &$crate::$set<$tp>, value: &$tp) -> bool;
+                    }
+                    func(this, value)
+                }
+
+                unsafe fn __insert(this: &mut $crate::$set<$tp>, value: &mut $tp) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _insert>])]
+                        fn func(this: &mut $crate::$set<$tp>, value: &mut $tp) -> bool;
+                    }
+                    func(this, value)
+                }
+
+                unsafe fn __remove(this: &mut $crate::$set<$tp>, value: &$tp) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _remove>])]
+                        fn func(this: &mut $crate::$set<$tp>, value: &$tp) -> bool;
+                    }
+                    func(this, value)
+                }
+
+                unsafe fn __clear(this: &mut $crate::$set<$tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _clear>])]
+                        fn func(this: &mut $crate::$set<$tp>);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __iter_new(this: &$crate::$set<$tp>, it: *mut $crate::RawIter) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _iter_new>])]
+                        fn func(this: &$crate::$set<$tp>, it: *mut $crate::RawIter);
+                    }
+                    func(this, it)
+                }
+
+                unsafe fn __iter_next(this: &$crate::$set<$tp>, it: &mut $crate::RawIter) -> *const $tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _iter_next>])]
+                        fn func(this: &$crate::$set<$tp>, it: &mut $crate::RawIter) -> *const $tp;
+                    }
+                    func(this, it)
+                }
+
+                $($extra)*
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_set {
+    ($link_name:ident, $tp:ty) => {
+        $crate::genrs_set_shims!(SetElement, CxxSet, $link_name, $tp, {});
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_unordered_set {
+    ($link_name:ident, $tp:ty) => {
+        $crate::genrs_set_shims!(UnorderedSetElement, CxxUnorderedSet, $link_name, $tp, {
+            unsafe fn __reserve(this: &mut $crate::CxxUnorderedSet<$tp>, n: usize) {
+                extern "C" {
+                    #[link_name=stringify!([<$link_name _reserve>])]
+                    fn func(this: &mut $crate::CxxUnorderedSet<$tp>, n: usize);
+                }
+                func(this, n)
+            }
+        });
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_deque {
+    ($link_name:ident, $tp:ty) => {
+        paste::paste! {
+            impl $crate::DequeElement for $tp {
+                unsafe fn __unique_drop(this: *mut core::ffi::c_void) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _unique_delete>])]
+                        fn func(this: *mut core::ffi::c_void);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __new(out: *mut $crate::UniquePtr<$crate::CxxDeque<$tp>>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new>])]
+                        fn func(out: *mut $crate::UniquePtr<$crate::CxxDeque<$tp>>);
+                    }
+                    func(out)
+                }
+
+                unsafe fn __size(this: &$crate::CxxDeque<$tp>) -> usize {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _size>])]
+                        fn func(this: &$crate::CxxDeque<$tp>) -> usize;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __get_unchecked(this: &$crate::CxxDeque<$tp>, pos: usize) -> &$tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get>])]
+                        fn func(this: &$crate::CxxDeque<$tp>, pos: usize) -> &$tp;
+                    }
+                    func(this, pos)
+                }
+
+                unsafe fn __get_unchecked_mut(this: &mut $crate::CxxDeque<$tp>, pos: usize) -> &mut $tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get_mut>])]
+                        fn func(this: &mut $crate::CxxDeque<$tp>, pos: usize) -> &mut $tp;
+                    }
+                    func(this, pos)
+                }
+
+                unsafe fn __push_back(this: &mut $crate::CxxDeque<$tp>, value: &mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _push_back>])]
+                        fn func(this: &mut $crate::CxxDeque<$tp>, value: &mut $tp);
+                    }
+                    func(this, value)
+                }
+
+                unsafe fn __push_front(this: &mut $crate::CxxDeque<$tp>, value: &mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _push_front>])]
+                        fn func(this: &mut $crate::CxxDeque<$tp>, value: &mut $tp);
+                    }
+                    func(this, value)
+                }
+
+                unsafe fn __pop_back(this: &mut $crate::CxxDeque<$tp>, out: *mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _pop_back>])]
+                        fn func(this: &mut $crate::CxxDeque<$tp>, out: *mut $tp);
+                    }
+                    func(this, out)
+                }
+
+                unsafe fn __pop_front(this: &mut $crate::CxxDeque<$tp>, out: *mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _pop_front>])]
+                        fn func(this: &mut $crate::CxxDeque<$tp>, out: *mut $tp);
+                    }
+                    func(this, out)
+                }
+
+                unsafe fn __clear(this: &mut $crate::CxxDeque<$tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _clear>])]
+                        fn func(this: &mut $crate::CxxDeque<$tp>);
+                    }
+                    func(this)
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_list {
+    ($link_name:ident, $tp:ty) => {
+        paste::paste! {
+            impl $crate::ListElement for $tp {
+                unsafe fn __unique_drop(this: *mut core::ffi::c_void) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _unique_delete>])]
+                        fn func(this: *mut core::ffi::c_void);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __new(out: *mut $crate::UniquePtr<$crate::CxxList<$tp>>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new>])]
+                        fn func(out: *mut $crate::UniquePtr<$crate::CxxList<$tp>>);
+                    }
+                    func(out)
+                }
+
+                unsafe fn __size(this: &$crate::CxxList<$tp>) -> usize {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _size>])]
+                        fn func(this: &$crate::CxxList<$tp>) -> usize;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __front(this: &$crate::CxxList<$tp>) -> *const $tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _front>])]
+                        fn func(this: &$crate::CxxList<$tp>) -> *const $tp;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __back(this: &$crate::CxxList<$tp>) -> *const $tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _back>])]
+                        fn func(this: &$crate::CxxList<$tp>) -> *const $tp;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __push_back(this: &mut $crate::CxxList<$tp>, value: &mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _push_back>])]
+                        fn func(this: &mut $crate::CxxList<$tp>, value: &mut $tp);
+                    }
+                    func(this, value)
+                }
+
+                unsafe fn __push_front(this: &mut $crate::CxxList<$tp>, value: &mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _push_front>])]
+                        fn func(this: &mut $crate::CxxList<$tp>, value: &mut $tp);
+                    }
+                    func(this, value)
+                }
+
+                unsafe fn __pop_back(this: &mut $crate::CxxList<$tp>, out: *mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _pop_back>])]
+                        fn func(this: &mut $crate::CxxList<$tp>, out: *mut $tp);
+                    }
+                    func(this, out)
+                }
+
+                unsafe fn __pop_front(this: &mut $crate::CxxList<$tp>, out: *mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _pop_front>])]
+                        fn func(this: &mut $crate::CxxList<$tp>, out: *mut $tp);
+                    }
+                    func(this, out)
+                }
+
+                unsafe fn __clear(this: &mut $crate::CxxList<$tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _clear>])]
+                        fn func(this: &mut $crate::CxxList<$tp>);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __splice(this: &mut $crate::CxxList<$tp>, pos: usize, other: &mut $crate::CxxList<$tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _splice>])]
+                        fn func(this: &mut $crate::CxxList<$tp>, pos: usize, other: &mut $crate::CxxList<$tp>);
+                    }
+                    func(this, pos, other)
+                }
+
+                unsafe fn __iter_new(this: &$crate::CxxList<$tp>, begin_it: *mut $crate::RawIter, end_it: *mut $crate::RawIter) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _iter_new>])]
+                        fn func(this: &$crate::CxxList<$tp>, begin_it: *mut $crate::RawIter, end_it: *mut $crate::RawIter);
+                    }
+                    func(this, begin_it, end_it)
+                }
+
+                unsafe fn __iter_next(it: &mut $crate::RawIter, end: &$crate::RawIter) -> *const $tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _iter_next>])]
+                        fn func(it: &mut $crate::RawIter, end: &$crate::RawIter) -> *const $tp;
+                    }
+                    func(it, end)
+                }
+
+                unsafe fn __iter_next_back(it: &$crate::RawIter, end: &mut $crate::RawIter) -> *const $tp {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _iter_next_back>])]
+                        fn func(it: &$crate::RawIter, end: &mut $crate::RawIter) -> *const $tp;
+                    }
+                    func(it, end)
+                }
+            }
+        }
+    };
+}
+
+/// Declares `pub struct $name` naming a `std::function` signature for
+/// `CxxFunction`, with the shims of `genc_function`.
+///
+/// ```no_run
+/// use rxx::{genrs_function, CxxFunction};
+///
+/// genrs_function!(AdderFn, rxx_function_i64_i64, fn(x: i64) -> i64);
+///
+/// fn apply(f: &CxxFunction<AdderFn>, x: i64) -> i64 {
+///     f.call((x,))
+/// }
+///
+/// fn adder(n: i64) -> CxxFunction<AdderFn> {
+///     CxxFunction::<AdderFn>::from_rust(Box::new(move |x| x + n))
+/// }
+/// ```
+#[macro_export]
+macro_rules! genrs_function {
+    ($name:ident, $link_name:ident, fn($($arg:ident: $arg_tp:ty),*) -> $ret_tp:ty) => {
+        $crate::genrs_function!(@impl $name, $link_name, ($($arg_tp),*) -> $ret_tp, {
+            unsafe fn __call(this: &$crate::CxxFunction<Self>, ($($arg,)*): Self::Args) -> $ret_tp {
+                extern "C" {
+                    #[link_name=stringify!([<$link_name _call>])]
+                    fn func(this: &$crate::CxxFunction<$name>, $($arg: $arg_tp,)* __ret: *mut $ret_tp);
+                }
+                let mut __ret = core::mem::MaybeUninit::<$ret_tp>::uninit();
+                func(this, $($arg,)* __ret.as_mut_ptr());
+                __ret.assume_init()
+            }
+
+            unsafe fn __from_rust(f: Box<Self::Closure>, out: *mut $crate::CxxFunction<Self>) {
+                type Ctx = std::sync::Mutex<Box<dyn FnMut($($arg_tp),*) -> $ret_tp + Send>>;
+
+                unsafe extern "C" fn call(ctx: *mut core::ffi::c_void, $($arg: $arg_tp,)* __ret: *mut $ret_tp) {
+                    let ctx = &*(ctx as *const Ctx);
+                    // a panic in `f` aborts, the mutex is never poisoned
+                    let mut f = ctx.lock().unwrap();
+                    __ret.write(f($($arg),*));
+                }
+
+                unsafe extern "C" fn delete(ctx: *mut core::ffi::c_void) {
+                    drop(Box::from_raw(ctx as *mut Ctx));
+                }
+
+                extern "C" {
+                    #[link_name=stringify!([<$link_name _from_rust>])]
+                    fn func(
+                        ctx: *mut core::ffi::c_void,
+                        delete: unsafe extern "C" fn(*mut core::ffi::c_void),
+                        call: unsafe extern "C" fn(*mut core::ffi::c_void, $($arg_tp,)* *mut $ret_tp),
+                        out: *mut $crate::CxxFunction<$name>,
+                    );
+                }
+                let ctx = Box::into_raw(Box::new(Ctx::new(f)));
+                func(ctx as *mut core::ffi::c_void, delete, call, out)
+            }
+        });
+    };
+
+    ($name:ident, $link_name:ident, fn($($arg:ident: $arg_tp:ty),*)) => {
+        $crate::genrs_function!(@impl $name, $link_name, ($($arg_tp),*) -> (), {
+            unsafe fn __call(this: &$crate::CxxFunction<Self>, ($($arg,)*): Self::Args) {
+                extern "C" {
+                    #[link_name=stringify!([<$link_name _call>])]
+                    fn func(this: &$crate::CxxFunction<$name>, $($arg: $arg_tp),*);
+                }
+                func(this, $($arg),*)
+            }
+
+            unsafe fn __from_rust(f: Box<Self::Closure>, out: *mut $crate::CxxFunction<Self>) {
+                type Ctx = std::sync::Mutex<Box<dyn FnMut($($arg_tp),*) + Send>>;
+
+                unsafe extern "C" fn call(ctx: *mut core::ffi::c_void, $($arg: $arg_tp),*) {
+                    let ctx = &*(ctx as *const Ctx);
+                    // a panic in `f` aborts, the mutex is never poisoned
+                    let mut f = ctx.lock().unwrap();
+                    f($($arg),*)
+                }
+
+                unsafe extern "C" fn delete(ctx: *mut core::ffi::c_void) {
+                    drop(Box::from_raw(ctx as *mut Ctx));
+                }
+
+                extern "C" {
+                    #[link_name=stringify!([<$link_name _from_rust>])]
+                    fn func(
+                        ctx: *mut core::ffi::c_void,
+                        delete: unsafe extern "C" fn(*mut core::ffi::c_void),
+                        call: unsafe extern "C" fn(*mut core::ffi::c_void, $($arg_tp),*),
+                        out: *mut $crate::CxxFunction<$name>,
+                    );
+                }
+                let ctx = Box::into_raw(Box::new(Ctx::new(f)));
+                func(ctx as *mut core::ffi::c_void, delete, call, out)
+            }
+        });
+    };
+
+    (@impl $name:ident, $link_name:ident, ($($arg_tp:ty),*) -> $ret_tp:ty, { $($call:tt)* }) => {
+        /// Signature of a C++ `std::function`, see `CxxFunction`.
+        pub struct $name;
+
+        paste::paste! {
+            impl $crate::FunctionSig for $name {
+                type Args = ($($arg_tp,)*);
+                type Output = $ret_tp;
+                type Closure = dyn FnMut($($arg_tp),*) -> $ret_tp + Send;
+
+                unsafe fn __drop(this: &mut $crate::CxxFunction<Self>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _delete>])]
+                        fn func(this: &mut $crate::CxxFunction<$name>);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __clone(this: &$crate::CxxFunction<Self>, out: *mut $crate::CxxFunction<Self>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _clone>])]
+                        fn func(this: &$crate::CxxFunction<$name>, out: *mut $crate::CxxFunction<$name>);
+                    }
+                    func(this, out)
+                }
+
+                unsafe fn __new_empty(out: *mut $crate::CxxFunction<Self>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _new_empty>])]
+                        fn func(out: *mut $crate::CxxFunction<$name>);
+                    }
+                    func(out)
+                }
+
+                unsafe fn __is_empty(this: &$crate::CxxFunction<Self>) -> bool {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _is_empty>])]
+                        fn func(this: &$crate::CxxFunction<$name>) -> bool;
+                    }
+                    func(this)
+                }
+
+                $($call)*
+            }
+        }
+    };
+}
+
+/// Declares `pub struct $name` as a `CxxCallback` and `CxxCallbackHandle`
+/// signature, for arguments passed by reference which the `fn` pointer
+/// signatures can't take. Closures need annotated arguments to be general
+/// over the lifetimes, e.g. `|word: &CxxString| ..`.
+#[macro_export]
+macro_rules! genrs_callback {
+    ($name:ident, fn($($arg:ident: $arg_tp:ty),*)) => {
+        $crate::genrs_callback!($name, fn($($arg: $arg_tp),*) -> ());
+    };
+
+    ($name:ident, fn($($arg:ident: $arg_tp:ty),*) -> $ret_tp:ty) => {
+        /// Signature of a C++ callback, see `CxxCallback`.
+        pub struct $name;
+
+        impl<C> $crate::CallbackSig<C> for $name
+        where
+            C: FnMut($($arg_tp),*) -> $ret_tp,
+        {
+            fn __trampoline() -> *const core::ffi::c_void {
+                unsafe extern "C" fn trampoline<C>(ctx: *mut core::ffi::c_void, $($arg: $arg_tp,)* ret: *mut $ret_tp) -> bool
+                where
+                    C: FnMut($($arg_tp),*) -> $ret_tp,
+                {
+                    let f = &mut *(ctx as *mut C);
+                    $crate::__call_callback(ret, || f($($arg),*))
+                }
+                trampoline::<C> as *const core::ffi::c_void
+            }
+        }
+
+        impl<C> $crate::CCallbackSig<C> for $name
+        where
+            C: Fn($($arg_tp),*) -> $ret_tp + Send + Sync + 'static,
+        {
+            fn __trampoline() -> *const core::ffi::c_void {
+                unsafe extern "C" fn trampoline<C>(ctx: *mut core::ffi::c_void, $($arg: $arg_tp),*) -> $ret_tp
+                where
+                    C: Fn($($arg_tp),*) -> $ret_tp,
+                {
+                    let f = &*(ctx as *const C);
+                    f($($arg),*)
+                }
+                trampoline::<C> as *const core::ffi::c_void
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! genrs_vector {
+    ($link_name:ident, $tp:ty) => {
+        paste::paste! {
+            impl $crate::VectorElement for $tp {
+                unsafe fn __drop(this: &mut $crate::CxxVector<$tp>) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _delete>])]
+                        fn func(this: &mut $crate::CxxVector<$tp>);
+                    }
+                    func(this)
+                }
+
+                unsafe fn __size(this: &$crate::CxxVector<$tp>) -> usize {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _size>])]
+                        fn func(this: &$crate::CxxVector<$tp>) -> usize;
+                    }
+                    func(this)
+                }
+
+                unsafe fn __get_unchecked(this: &$crate::CxxVector<$tp>, pos: usize) -> &Self {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get>])]
+                        fn func(this: &$crate::CxxVector<$tp>, pos: usize) -> &$tp;
+                    }
+                    func(this, pos)
+                }
+
+                unsafe fn __get_unchecked_mut(this: &mut $crate::CxxVector<$tp>, pos: usize) -> &mut Self {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _get_mut>])]
+                        fn func(this: &mut $crate::CxxVector<$tp>, pos: usize) -> &mut $tp;
+                    }
+                    func(this, pos)
+                }
+
+                unsafe fn __push_back(this: &mut $crate::CxxVector<$tp>, value: &mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _push_back>])]
+                        fn func(this: &mut $crate::CxxVector<$tp>, value: &mut $tp);
+                    }
+                    func(this, value)
+                }
+
+                unsafe fn __pop_back(this: &mut $crate::CxxVector<$tp>, value: *mut $tp) {
+                    extern "C" {
+                        #[link_name=stringify!([<$link_name _pop_back>])]
+                        fn func(this: &mut $crate::CxxVector<$tp>, value: *mut $tp);
+                    }
+                    func(this, value)
+                }
+
+            }
+        }
+    }
+}
+
+genrs_unique_ptr!(rxx_unique_string, crate::CxxString);
+genrs_shared_ptr!(rxx_shared_string, crate::CxxString);
+genrs_weak_ptr!(rxx_weak_string, crate::CxxString);
+genrs_unique_ptr!(rxx_unique_stringstream, crate::CxxStringStream);
+
+genrs_vector!(rxx_vector_complex_f32, crate::CxxComplex<f32>);
+genrs_vector!(rxx_vector_complex_f64, crate::CxxComplex<f64>);
+
+#[cfg(all(feature = "cxx17", unix))]
+genrs_unique_ptr!(rxx_unique_path, crate::CxxPath);
+#[cfg(all(feature = "cxx17", unix))]
+genrs_vector!(rxx_vector_path, crate::CxxPath);
@@ -7,23 +7,101 @@ pub use unique_ptr::*;
 pub mod shared_ptr;
 pub use shared_ptr::*;
 
+pub mod atomic_shared_ptr;
+pub use atomic_shared_ptr::*;
+
 pub mod weak_ptr;
 pub use weak_ptr::*;
 
+pub mod rust_type;
+pub use rust_type::*;
+
+pub mod shared_from_this;
+pub use shared_from_this::*;
+
+pub mod pointer_cast;
+pub use pointer_cast::*;
+
 pub mod cxx_string;
 pub use cxx_string::*;
 
 pub mod cxx_vector;
 pub use cxx_vector::*;
 
+pub mod cxx_any;
+pub use cxx_any::*;
+
+pub mod cxx_tuple;
+pub use cxx_tuple::*;
+
+pub mod cxx_array;
+pub use cxx_array::*;
+
+pub mod cxx_unordered_map;
+pub use cxx_unordered_map::*;
+
+pub mod cxx_map;
+pub use cxx_map::*;
+
+pub mod cxx_set;
+pub use cxx_set::*;
+
+pub mod cxx_deque;
+pub use cxx_deque::*;
+
+pub mod cxx_list;
+pub use cxx_list::*;
+
+pub mod cxx_function;
+pub use cxx_function::*;
+
+pub mod cxx_callback;
+pub use cxx_callback::*;
+
+#[cfg(feature = "cxx17")]
+pub mod cxx_optional;
+#[cfg(feature = "cxx17")]
+pub use cxx_optional::*;
+
+#[cfg(feature = "cxx17")]
+pub mod cxx_variant;
+#[cfg(feature = "cxx17")]
+pub use cxx_variant::*;
+
+#[cfg(all(feature = "cxx17", unix))]
+pub mod cxx_path;
+#[cfg(all(feature = "cxx17", unix))]
+pub use cxx_path::*;
+
+pub mod cxx_complex;
+pub use cxx_complex::*;
+
+pub mod cxx_error_code;
+pub use cxx_error_code::*;
+
+pub mod cxx_stream;
+pub use cxx_stream::*;
+
+pub mod cxx_chrono;
+pub use cxx_chrono::*;
+
 pub mod ffi;
 pub use ffi::*;
 
 #[cfg(test)]
 mod tests {
     use core::ffi::c_void;
+    use core::time::Duration;
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+    use std::ffi::OsStr;
+    use std::io::{self, BufRead, Read, Write};
     use std::marker::PhantomData;
     use std::mem::MaybeUninit;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     use super::*;
 
@@ -44,8 +122,141 @@ mod tests {
 
     genrs_unique_ptr!(rxx_unique_i64, i64);
     genrs_shared_ptr!(rxx_shared_i64, i64);
+    genrs_make_shared!(unsafe rxx_shared_i64, i64);
+    genrs_atomic_shared_ptr!(rxx_shared_i64, i64);
     genrs_weak_ptr!(rxx_weak_i64, i64);
     genrs_vector!(rxx_vector_i64, i64);
+    genrs_unordered_map!(rxx_unordered_map_string_i64, CxxString, i64);
+    genrs_unordered_map!(rxx_unordered_map_i64_vector_i64, i64, CxxVector<i64>);
+    genrs_unordered_map!(rxx_unordered_map_i64_i64, i64, i64);
+
+    fn new_string_map() -> UniquePtr<CxxUnorderedMap<CxxString, i64>> {
+        extern "C" {
+            #[link_name = "rxx_dummy_cpp_new_string_map"]
+            fn __func(out: *mut UniquePtr<CxxUnorderedMap<CxxString, i64>>);
+        }
+        let mut out = MaybeUninit::uninit();
+        unsafe {
+            __func(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    genrs_fn!(fn rxx_dummy_cpp_string_map_sum(m: &CxxUnorderedMap<CxxString, i64>) -> i64, cret=atomic);
+
+    genrs_map!(rxx_map_i64_f64, i64, f64);
+    genrs_map!(rxx_map_string_vector_i64, CxxString, CxxVector<i64>);
+    genrs_multimap!(rxx_multimap_i64_i64, i64, i64);
+
+    genrs_set!(rxx_set_string, CxxString);
+    genrs_set!(rxx_set_i64, i64);
+    genrs_unordered_set!(rxx_unordered_set_i64, i64);
+
+    fn new_tags() -> UniquePtr<CxxSet<CxxString>> {
+        extern "C" {
+            #[link_name = "rxx_dummy_cpp_new_tags"]
+            fn __func(out: *mut UniquePtr<CxxSet<CxxString>>);
+        }
+        let mut out = MaybeUninit::uninit();
+        unsafe {
+            __func(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    genrs_fn!(fn rxx_dummy_cpp_unordered_set_sum(s: &CxxUnorderedSet<i64>) -> i64, cret=atomic);
+
+    genrs_deque!(rxx_deque_i64, i64);
+    genrs_deque!(rxx_deque_vector_i64, CxxVector<i64>);
+    genrs_list!(rxx_list_i64, i64);
+
+    genrs_fn!(fn rxx_dummy_cpp_deque_sum(d: &CxxDeque<i64>) -> i64, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_new_list(n: i64) -> UniquePtr<CxxList<i64>>);
+
+    genrs_function!(AdderFn, rxx_function_i64_i64, fn(x: i64) -> i64);
+    genrs_function!(
+        IotaFn,
+        rxx_function_i64_vector_i64,
+        fn(n: i64) -> CxxVector<i64>
+    );
+    genrs_function!(SinkFn, rxx_function_i64_void, fn(x: i64));
+
+    genrs_fn!(fn rxx_dummy_cpp_make_adder(n: i64) -> CxxFunction<AdderFn>);
+    genrs_fn!(fn rxx_dummy_cpp_apply_twice(f: &CxxFunction<AdderFn>, x: i64) -> i64, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_make_iota(start: i64) -> CxxFunction<IotaFn>);
+    genrs_fn!(fn rxx_dummy_cpp_make_sink(total: *mut i64) -> CxxFunction<SinkFn>);
+
+    genrs_fn!(fn rxx_dummy_cpp_call_copies(f: &CxxFunction<SinkFn>, n: i64));
+
+    genrs_fn!(fn rxx_dummy_cpp_for_each_node(nodes: &CxxVector<i64>, visit: CxxCallback<'_, fn(i64)>));
+    genrs_callback!(WordFn, fn(word: &CxxString));
+    genrs_fn!(fn rxx_dummy_cpp_for_each_word(n: i64, visit: CxxCallback<'_, WordFn>));
+    genrs_fn!(fn rxx_dummy_cpp_fold(n: i64, init: i64, f: CxxCallback<'_, fn(i64, i64) -> i64>) -> i64, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_collect(n: i64, gen: CxxCallback<'_, fn(i64) -> CxxVector<i64>>) -> CxxVector<i64>);
+
+    genrs_fn!(fn rxx_dummy_cpp_register_handler(cb: &CxxCallbackHandle<fn(i64)>));
+    genrs_fn!(fn rxx_dummy_cpp_fire_event(ev: i64));
+    genrs_fn!(fn rxx_dummy_cpp_unregister_handlers());
+    genrs_fn!(fn rxx_dummy_cpp_call_c_callback(x: i64, cb: &CxxCallbackHandle<fn(i64) -> i64>) -> i64, cret=atomic);
+
+    genrs_fn!(fn rxx_dummy_cpp_complex_mul(a: CxxComplex<f64>, b: CxxComplex<f64>) -> CxxComplex<f64>, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_complex_conj_f32(c: CxxComplex<f32>) -> CxxComplex<f32>, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_spectrum(n: i64) -> CxxVector<CxxComplex<f64>>);
+    genrs_fn!(fn rxx_dummy_cpp_spectrum_energy(s: &CxxVector<CxxComplex<f64>>) -> f64, cret=atomic);
+
+    genrs_fn!(fn rxx_dummy_cpp_checked_div(a: i64, b: i64) -> Result<i64, CxxErrorCode>, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_remove_missing(id: i64) -> Result<(), CxxErrorCode>);
+    genrs_fn!(fn rxx_dummy_cpp_checked_range(n: i64) -> Result<CxxVector<i64>, CxxErrorCode>);
+    genrs_fn!(fn rxx_dummy_cpp_parse_port(port: i64) -> Result<i64, CxxErrorCode>, cret=atomic);
+
+    genrs_fn!(fn rxx_dummy_cpp_sum_words(input: &mut CxxIStream) -> i64, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_log_to(out: &mut CxxOStream, n: i64));
+    genrs_fn!(fn rxx_dummy_cpp_null_ostream() -> &'static mut CxxOStream, cret=atomic);
+
+    genrs_fn!(fn rxx_dummy_cpp_double_timeout(timeout: CxxMilliseconds) -> CxxMilliseconds, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_add_hour(t: CxxSystemTime) -> CxxSystemTime, cret=atomic);
+    genrs_fn!(fn rxx_dummy_cpp_steady_millis(start: CxxSteadyInstant, end: CxxSteadyInstant) -> i64, cret=atomic);
+
+    genrs_fn!(fn rxx_dummy_cpp_new_series(n: i64) -> UniquePtr<CxxMap<i64, f64>>);
+
+    genrs_vector!(rxx_vector_array_f64_3, CxxArray<f64, 3>);
+
+    #[repr(C)]
+    struct DummyPoint {
+        id: i64,
+        pos: CxxArray<f64, 3>,
+    }
+
+    genrs_fn!(fn rxx_dummy_cpp_cross(a: CxxArray<f64, 3>, b: CxxArray<f64, 3>) -> CxxArray<f64, 3>);
+    genrs_fn!(fn rxx_dummy_cpp_new_points(n: i32) -> CxxVector<CxxArray<f64, 3>>);
+    genrs_fn!(fn rxx_dummy_cpp_point_norm2(p: &DummyPoint) -> f64, cret=atomic);
+
+    genrs_tuple!(rxx_tuple_i32_f64_vector_i64, (i32, f64, CxxVector<i64>));
+
+    genrs_fn!(fn rxx_dummy_cpp_new_pair(a: i32) -> CxxPair<i32, f64>);
+    genrs_fn!(fn rxx_dummy_cpp_new_pair_vector(a: i64) -> CxxPair<i64, CxxVector<i64>>);
+    genrs_fn!(fn rxx_dummy_cpp_new_tuple(a: i32) -> CxxTuple<(i32, f64, CxxVector<i64>)>);
+    genrs_fn!(fn rxx_dummy_cpp_tuple_sum(t: &CxxTuple<(i32, f64, CxxVector<i64>)>) -> f64, cret=atomic);
+
+    genrs_any!(rxx_any_i64, i64);
+    genrs_optional!(rxx_optional_i64, i64);
+    genrs_optional!(rxx_optional_vector_i64, CxxVector<i64>);
+
+    genrs_variant!(
+        rxx_DummyMsg,
+        enum DummyMsg {
+            Int(i64),
+            Float(f64),
+            Values(CxxVector<i64>),
+        }
+    );
+
+    genrs_fn!(fn rxx_dummy_cpp_new_msg(kind: i64) -> CxxVariant<DummyMsg>);
+    genrs_fn!(fn rxx_dummy_cpp_msg_sum(msg: &CxxVariant<DummyMsg>) -> f64, cret=atomic);
+    genrs_any!(rxx_any_string, CxxString);
+    genrs_type_info!(rxx_type_info_config, DummyConfig);
+    genrs_shared_ptr!(rxx_shared_vector_i64, CxxVector<i64>);
+    genrs_make_shared!(unsafe rxx_shared_vector_i64, CxxVector<i64>);
 
     fn new_unique_i64(v: i64) -> UniquePtr<i64> {
         extern "C" {
@@ -110,6 +321,168 @@ mod tests {
         }
     }
 
+    #[repr(C)]
+    struct DummyConfig {
+        values: CxxVector<i64>,
+        id: i64,
+    }
+
+    genrs_shared_ptr!(rxx_shared_config, DummyConfig);
+
+    fn new_shared_config(id: i64) -> SharedPtr<DummyConfig> {
+        extern "C" {
+            #[link_name = "rxx_dummy_new_shared_config"]
+            fn __func(id: i64, out: *mut SharedPtr<DummyConfig>);
+        }
+        let mut out = MaybeUninit::<SharedPtr<DummyConfig>>::uninit();
+        unsafe {
+            __func(id, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    #[repr(C)]
+    struct DummyBase {
+        _private: [u8; 0],
+    }
+
+    #[repr(C)]
+    struct DummyDerived {
+        _private: [u8; 0],
+    }
+
+    genrs_unique_ptr!(rxx_unique_DummyBase, DummyBase);
+    genrs_shared_ptr!(rxx_shared_DummyBase, DummyBase);
+    genrs_unique_ptr!(rxx_unique_DummyDerived, DummyDerived);
+    genrs_shared_ptr!(rxx_shared_DummyDerived, DummyDerived);
+    genrs_pointer_cast!(rxx_DummyDerived_DummyBase, DummyBase, DummyDerived);
+
+    genrs_fn!(DummyBase;; pub fn value(&self) -> i64, cret=atomic, ln=rxx_DummyBase_value);
+    genrs_fn!(DummyDerived;; pub fn derived_value(&self) -> i64, cret=atomic, ln=rxx_DummyDerived_derived_value);
+
+    fn new_shared_base() -> SharedPtr<DummyBase> {
+        extern "C" {
+            #[link_name = "rxx_dummy_new_shared_base"]
+            fn __func(out: *mut SharedPtr<DummyBase>);
+        }
+        let mut out = MaybeUninit::<SharedPtr<DummyBase>>::uninit();
+        unsafe {
+            __func(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    fn new_shared_derived(val: i64) -> SharedPtr<DummyBase> {
+        extern "C" {
+            #[link_name = "rxx_dummy_new_shared_derived"]
+            fn __func(val: i64, out: *mut SharedPtr<DummyBase>);
+        }
+        let mut out = MaybeUninit::<SharedPtr<DummyBase>>::uninit();
+        unsafe {
+            __func(val, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    fn new_unique_derived(val: i64) -> UniquePtr<DummyBase> {
+        extern "C" {
+            #[link_name = "rxx_dummy_new_unique_derived"]
+            fn __func(val: i64, out: *mut UniquePtr<DummyBase>);
+        }
+        let mut out = MaybeUninit::<UniquePtr<DummyBase>>::uninit();
+        unsafe {
+            __func(val, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    #[repr(C)]
+    struct DummyNode {
+        _private: [u8; 0],
+    }
+
+    genrs_unique_ptr!(rxx_unique_DummyNode, DummyNode);
+    genrs_shared_ptr!(rxx_shared_DummyNode, DummyNode);
+    genrs_weak_ptr!(rxx_weak_DummyNode, DummyNode);
+    genrs_shared_from_this!(rxx_DummyNode, DummyNode);
+
+    genrs_fn!(DummyNode;; pub fn id(&self) -> i64, cret=atomic, ln=rxx_DummyNode_id);
+
+    fn new_shared_node(id: i64) -> SharedPtr<DummyNode> {
+        extern "C" {
+            #[link_name = "rxx_dummy_new_shared_node"]
+            fn __func(id: i64, out: *mut SharedPtr<DummyNode>);
+        }
+        let mut out = MaybeUninit::<SharedPtr<DummyNode>>::uninit();
+        unsafe {
+            __func(id, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    fn new_unique_node(id: i64) -> UniquePtr<DummyNode> {
+        extern "C" {
+            #[link_name = "rxx_dummy_new_unique_node"]
+            fn __func(id: i64, out: *mut UniquePtr<DummyNode>);
+        }
+        let mut out = MaybeUninit::<UniquePtr<DummyNode>>::uninit();
+        unsafe {
+            __func(id, out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    genrs_fn!(fn rxx_dummy_global_config() -> &'static AtomicSharedPtr<i64>, cret=atomic);
+    genrs_fn!(fn rxx_dummy_reload_config(val: i64));
+
+    fn new_plugin_i64(v: i64) -> (SharedPtr<CxxVoid>, &'static CxxTypeInfo) {
+        extern "C" {
+            #[link_name = "rxx_dummy_plugin_i64"]
+            fn __func(v: i64, out: *mut SharedPtr<CxxVoid>) -> &'static CxxTypeInfo;
+        }
+        let mut out = MaybeUninit::<SharedPtr<CxxVoid>>::uninit();
+        unsafe {
+            let tag = __func(v, out.as_mut_ptr());
+            (out.assume_init(), tag)
+        }
+    }
+
+    genrs_fn!(fn rxx_dummy_cpp_find_i64(v: &CxxVector<i64>, val: i64) -> CxxOptional<i64>);
+    genrs_fn!(fn rxx_dummy_cpp_value_or_i64(v: &CxxOptional<i64>, def: i64) -> i64, cret=atomic);
+
+    genrs_fn!(fn rxx_dummy_cpp_parent_path(p: &CxxPath) -> UniquePtr<CxxPath>);
+    genrs_fn!(fn rxx_dummy_cpp_path_parts(p: &CxxPath) -> CxxVector<CxxPath>);
+
+    fn new_any_string() -> CxxAny {
+        extern "C" {
+            #[link_name = "rxx_dummy_new_any_string"]
+            fn __func(out: *mut CxxAny);
+        }
+        let mut out = MaybeUninit::<CxxAny>::uninit();
+        unsafe {
+            __func(out.as_mut_ptr());
+            out.assume_init()
+        }
+    }
+
+    static HANDLER_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct RustHandler {
+        id: i64,
+    }
+
+    impl Drop for RustHandler {
+        fn drop(&mut self) {
+            HANDLER_DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    genrs_rust_type!(rxx_RustHandler, RustHandler);
+
+    genrs_fn!(fn rxx_dummy_register_handler(h: &SharedPtr<RustHandler>));
+    genrs_fn!(fn rxx_dummy_clear_handlers() -> usize, cret=atomic);
+    genrs_fn!(fn rxx_dummy_take_handler(h: &mut UniquePtr<RustHandler>));
+
     #[repr(C)]
     struct Dummy<'a> {
         data: *mut i64,
@@ -120,6 +493,9 @@ mod tests {
     genrs_fn!(Dummy<'_>;; pub fn get(&self, idx: usize) -> i64, cret=atomic, ln=rxx_Dummy_get);
     genrs_fn!(Dummy<'a>; impl<'a>; pub fn get_mut(&mut self, idx: usize) -> &'a mut i64, cret=atomic, ln=rxx_Dummy_get_mut);
     genrs_fn!(Dummy<'_>;; pub fn add(&mut self, val: i64), ln=rxx_Dummy_add);
+    genrs_fn!(Dummy<'_>;; pub fn checked_get(&self, idx: usize) -> Result<i64, CxxErrorCode>, cret=atomic, ln=rxx_Dummy_checked_get);
+    genrs_fn!(Dummy<'_>;; pub fn checked_add(&mut self, val: i64) -> Result<(), CxxErrorCode>, ln=rxx_Dummy_checked_add);
+    genrs_fn!(Dummy<'_>;; pub fn checked_prefix(&self, n: usize) -> Result<CxxVector<i64>, CxxErrorCode>, ln=rxx_Dummy_checked_prefix);
 
     #[test]
     fn test_cpp_fn() {
@@ -170,6 +546,19 @@ mod tests {
 
         b.add(3);
         assert_eq!(b.get(0), 11);
+
+        assert_eq!(b.checked_get(1), Ok(5));
+        let e = b.checked_get(4).unwrap_err();
+        assert_eq!(e.raw_os_error(), Some(34)); // ERANGE
+
+        assert_eq!(b.checked_add(1), Ok(()));
+        assert_eq!(b.get(2), 7);
+        let e = b.checked_add(-1).unwrap_err();
+        assert_eq!(io::Error::from(e).kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(b.get(2), 7);
+
+        assert_eq!(b.checked_prefix(2).map(|v| v.to_vec()), Ok(vec![12, 6]));
+        assert!(b.checked_prefix(5).is_err());
     }
 
     #[test]
@@ -200,12 +589,813 @@ mod tests {
 
         let mut b = o.clone();
         assert_eq!(*b, v);
+        assert_eq!(b.use_count(), 2);
+        assert!(unsafe { b.get_mut() }.is_none());
 
         let c = o.downgrade();
         assert_eq!(*c.upgrade(), v);
 
-        let bb = b.pin_mut();
-        assert_eq!(*bb, v);
+        drop(o);
+        assert_eq!(b.use_count(), 1);
+        // `c` is not upgraded while `bb` is alive
+        let mut bb = unsafe { b.get_mut() }.unwrap();
+        *bb = 5;
+        assert_eq!(*b, 5);
+        assert_eq!(*c.upgrade(), 5);
+    }
+
+    genrs_make_shared!(unsafe rxx_shared_string, CxxString);
+
+    #[test]
+    fn test_make_shared() {
+        let a = SharedPtr::<i64>::new(42);
+        assert_eq!(*a, 42);
+        assert_eq!(a.use_count(), 1);
+        assert_eq!(*SharedPtr::<i64>::new_default(), 0);
+
+        let v = SharedPtr::new(new_vector_i64(&[1, 2, 3]));
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+
+        let s = SharedPtr::<CxxString>::new_default();
+        assert_eq!(s.len(), 0);
+    }
+
+    #[test]
+    fn test_shared_ptr_project() {
+        let cfg = new_shared_config(7);
+        let values = cfg.project(|c| &c.values);
+        let id = cfg.project(|c| &c.id);
+        drop(cfg);
+
+        assert_eq!(values.as_slice(), &[1, 2, 3]);
+        assert_eq!(*id, 7);
+
+        let values2 = values.clone();
+        drop(values);
+        assert_eq!(values2.len(), 3);
+    }
+
+    #[test]
+    fn test_pointer_cast() {
+        let b = new_shared_derived(5);
+        assert_eq!(b.value(), 5);
+
+        let d = b.downcast::<DummyDerived>().unwrap();
+        assert_ne!(d.get_ptr() as usize, b.get_ptr() as usize);
+        assert_eq!(d.derived_value(), 10);
+
+        let b2 = d.upcast::<DummyBase>();
+        assert_eq!(b2.get_ptr(), b.get_ptr());
+        assert_eq!(b2.value(), 5);
+
+        let d2 = unsafe { b2.downcast_unchecked::<DummyDerived>() };
+        assert_eq!(d2.get_ptr(), d.get_ptr());
+
+        assert!(new_shared_base().downcast::<DummyDerived>().is_none());
+
+        let b = new_unique_derived(3);
+        let d = b.downcast::<DummyDerived>().ok().unwrap();
+        assert_eq!(d.derived_value(), 6);
+
+        let b = d.upcast::<DummyBase>();
+        assert_eq!(b.value(), 3);
+    }
+
+    #[test]
+    fn test_shared_from_this() {
+        let n = new_shared_node(3);
+        let node: &DummyNode = &n;
+
+        let s = node.shared_from_this().unwrap();
+        assert_eq!(s.get_ptr(), n.get_ptr());
+        assert_eq!(s.id(), 3);
+
+        let w = node.weak_from_this();
+        assert_eq!(w.upgrade().get_ptr(), n.get_ptr());
+
+        let u = new_unique_node(4);
+        assert!(matches!(u.shared_from_this(), Err(BadWeakPtr)));
+        assert!(u.weak_from_this().is_null());
+    }
+
+    #[test]
+    fn test_rust_type() {
+        let h = SharedPtr::from_rust(RustHandler { id: 1 });
+        assert_eq!(h.id, 1);
+        rxx_dummy_register_handler(&h);
+        drop(h);
+        assert_eq!(HANDLER_DROPS.load(Ordering::SeqCst), 0);
+        assert_eq!(rxx_dummy_clear_handlers(), 1);
+        assert_eq!(HANDLER_DROPS.load(Ordering::SeqCst), 1);
+
+        let a = Arc::new(RustHandler { id: 2 });
+        let s = SharedPtr::from_arc(a.clone());
+        assert_eq!(s.id, 2);
+        let s2 = s.clone();
+        drop(s);
+        assert_eq!(Arc::strong_count(&a), 2);
+        drop(s2);
+        assert_eq!(Arc::strong_count(&a), 1);
+
+        let mut u = UniquePtr::from_box(Box::new(RustHandler { id: 3 }));
+        assert_eq!(u.id, 3);
+        rxx_dummy_take_handler(&mut u);
+        assert!(u.is_null());
+        assert_eq!(HANDLER_DROPS.load(Ordering::SeqCst), 2);
+
+        drop(UniquePtr::from_box(Box::new(RustHandler { id: 4 })));
+        assert_eq!(HANDLER_DROPS.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_atomic_shared_ptr() {
+        let a = AtomicSharedPtr::new(new_shared_i64(1));
+        assert_eq!(*a.load(), 1);
+
+        a.store(new_shared_i64(2));
+        assert_eq!(*a.load(), 2);
+
+        let old = a.swap(new_shared_i64(3));
+        assert_eq!(*old, 2);
+
+        let cur = a.load();
+        assert_eq!(*a.compare_exchange(&old, new_shared_i64(4)).unwrap_err(), 3);
+        assert_eq!(*a.compare_exchange(&cur, new_shared_i64(5)).unwrap(), 3);
+        assert_eq!(*a.into_inner(), 5);
+
+        let config = rxx_dummy_global_config();
+        rxx_dummy_reload_config(7);
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        assert!(*config.load() >= 7);
+                    }
+                })
+            })
+            .collect();
+        for i in 8..100 {
+            rxx_dummy_reload_config(i);
+        }
+        for r in readers {
+            r.join().unwrap();
+        }
+        assert_eq!(*config.load(), 99);
+    }
+
+    #[test]
+    fn test_shared_void() {
+        let (p, tag) = new_plugin_i64(5);
+        assert!(p.downcast_tagged::<DummyConfig>(tag).is_none());
+
+        let v = p.downcast_tagged::<i64>(tag).unwrap();
+        assert_eq!(*v, 5);
+        assert_eq!(v.use_count(), 2);
+
+        let p2 = v.to_void();
+        drop(p);
+        assert_eq!(
+            *p2.downcast_tagged::<i64>(CxxTypeInfo::of::<i64>()).unwrap(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_any() {
+        let mut a = CxxAny::new(3i64);
+        assert!(a.has_value());
+        assert!(a.is::<i64>());
+        assert!(!a.is::<CxxString>());
+        assert_eq!(a.type_name(), CxxTypeInfo::of::<i64>().name());
+        assert!(a.downcast_ref::<CxxString>().is_none());
+
+        *a.downcast_mut::<i64>().unwrap() = 4;
+        let b = a.clone();
+        a.reset();
+        assert!(!a.has_value());
+        assert_eq!(b.downcast_ref::<i64>(), Some(&4));
+
+        let s = new_any_string();
+        assert!(s.is::<CxxString>());
+        assert_eq!(s.downcast_ref::<CxxString>().unwrap().to_str(), "any");
+        assert!(!CxxAny::default().has_value());
+    }
+
+    #[test]
+    fn test_optional() {
+        let v = rxx_dummy_cpp_new_vector_i64(4);
+        assert_eq!(rxx_dummy_cpp_find_i64(&v, 4).as_option(), Some(&0));
+        assert!(rxx_dummy_cpp_find_i64(&v, 9).is_none());
+
+        let mut o = CxxOptional::from(Some(7i64));
+        assert_eq!(rxx_dummy_cpp_value_or_i64(&o, 0), 7);
+        *o.as_option_mut().unwrap() += 1;
+        assert_eq!(o.take(), Some(8));
+        assert!(o.is_none());
+        assert_eq!(rxx_dummy_cpp_value_or_i64(&o, 0), 0);
+        assert_eq!(format!("{:?}", o), "nullopt");
+
+        let o = CxxOptional::some(rxx_dummy_cpp_new_vector_i64(3));
+        assert_eq!(o.as_option().map(|v| v.len()), Some(1));
+        let v: Option<CxxVector<i64>> = o.into();
+        assert_eq!(v.unwrap().as_slice(), &[3]);
+        assert!(CxxOptional::<CxxVector<i64>>::default().is_none());
+    }
+
+    #[test]
+    fn test_unordered_map() {
+        let mut m = new_string_map();
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.get("a"), Some(&1));
+        assert!(!m.contains_key("c"));
+
+        *m.get_mut("b").unwrap() += 10;
+        assert_eq!(m.insert("c", 3), None);
+        assert_eq!(m.insert("a", 4), Some(1));
+        assert_eq!(rxx_dummy_cpp_string_map_sum(&m), 19);
+
+        let mut keys = m.iter().map(|(k, v)| (k.to_str(), *v)).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, [("a", 4), ("b", 12), ("c", 3)]);
+
+        assert_eq!(m.remove("b"), Some(12));
+        assert_eq!(m.remove("b"), None);
+        m.clear();
+        assert!(m.is_empty());
+
+        let mut m = CxxUnorderedMap::<i64, CxxVector<i64>>::new();
+        m.reserve(2);
+        m.insert(1, rxx_dummy_cpp_new_vector_i64(1));
+        let k = 2;
+        m.insert(k, rxx_dummy_cpp_new_vector_i64(2));
+        m.get_mut(2).unwrap().push(3);
+        assert_eq!(m.get(k).unwrap().as_slice(), &[2, 3]);
+        assert_eq!(m.remove(1).unwrap().as_slice(), &[1]);
+
+        let h = HashMap::from([(1, 2), (3, 4)]);
+        let m = UniquePtr::<CxxUnorderedMap<i64, i64>>::from(h.clone());
+        assert_eq!(m.len(), 2);
+        assert_eq!(HashMap::from(&*m), h);
+    }
+
+    #[test]
+    fn test_map() {
+        let mut m = rxx_dummy_cpp_new_series(5);
+        assert_eq!(m.len(), 5);
+        assert_eq!(m.get(20), Some(&1.0));
+        assert_eq!(m.first(), Some((&10, &0.5)));
+        assert_eq!(m.last(), Some((&50, &2.5)));
+
+        let keys = |r: MapRange<'_, i64, f64>| r.map(|(k, _)| *k).collect::<Vec<_>>();
+        assert_eq!(keys(m.iter()), [10, 20, 30, 40, 50]);
+        assert_eq!(keys(m.range(15..40)), [20, 30]);
+        assert_eq!(keys(m.range(20..=40)), [20, 30, 40]);
+        assert_eq!(keys(m.range(..=20)), [10, 20]);
+        assert_eq!(keys(m.range(45..)), [50]);
+        // start past end is empty rather than undefined behaviour in C++
+        let (lo, hi) = (40, 20);
+        assert_eq!(keys(m.range(lo..hi)), []);
+        assert_eq!(keys(m.range(hi..hi)), []);
+
+        assert_eq!(m.insert(20, 7.0), Some(1.0));
+        assert_eq!(m.insert(60, 3.0), None);
+        *m.get_mut(60).unwrap() += 1.0;
+        assert_eq!(m.remove(10), Some(0.5));
+        assert!(!m.contains_key(10));
+
+        let b = BTreeMap::from(&*m);
+        assert_eq!(b.get(&60), Some(&4.0));
+        let m2 = UniquePtr::<CxxMap<i64, f64>>::from(b.clone());
+        assert_eq!(BTreeMap::from(&*m2), b);
+        m.clear();
+        assert!(m.first().is_none() && m.last().is_none());
+
+        let mut s = CxxMap::<CxxString, CxxVector<i64>>::new();
+        s.insert("b", rxx_dummy_cpp_new_vector_i64(2));
+        s.insert("a", rxx_dummy_cpp_new_vector_i64(1));
+        s.insert("c", rxx_dummy_cpp_new_vector_i64(3));
+        let keys = s
+            .range("a".."c")
+            .map(|(k, _)| k.to_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["a", "b"]);
+        assert_eq!(s.remove("a").unwrap().as_slice(), &[1]);
+
+        let mut mm = CxxMultiMap::<i64, i64>::new();
+        mm.extend([(1, 10), (2, 20), (1, 11), (0, 0), (1, 12), (1, 13)]);
+        assert_eq!(mm.len(), 6);
+        assert_eq!(mm.get(1), Some(&10));
+        assert_eq!(mm.get(3), None);
+        *mm.get_mut(1).unwrap() += 5;
+        assert_eq!(mm.get_all(1).copied().collect::<Vec<_>>(), [15, 11, 12, 13]);
+        assert_eq!(mm.remove(0), 1);
+        assert_eq!(
+            BTreeMap::from(&*mm),
+            BTreeMap::from([(1, vec![15, 11, 12, 13]), (2, vec![20])])
+        );
+        assert_eq!(mm.remove(1), 4);
+        assert_eq!(mm.len(), 1);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut tags = new_tags();
+        assert_eq!(tags.len(), 3);
+        assert!(tags.contains("io"));
+        assert!(!tags.contains("gpu"));
+        let sorted = tags.iter().map(|s| s.to_str()).collect::<Vec<_>>();
+        assert_eq!(sorted, ["cpu", "io", "urgent"]);
+        assert!(tags.remove("io"));
+        assert!(!tags.remove("io"));
+        assert_eq!(tags.len(), 2);
+
+        let mut s = [3, 1, 2].into_iter().collect::<UniquePtr<CxxSet<i64>>>();
+        assert!(!s.insert(2));
+        assert!(s.insert(0));
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(BTreeSet::from(&*s), BTreeSet::from([0, 1, 2, 3]));
+        assert_eq!(format!("{:?}", s), "{0, 1, 2, 3}");
+        s.clear();
+        assert!(s.is_empty());
+
+        let mut u = UniquePtr::<CxxUnorderedSet<i64>>::from(HashSet::from([5, 6, 7]));
+        assert!(u.insert(8));
+        assert!(!u.insert(5));
+        assert!(u.contains(8));
+        assert_eq!(rxx_dummy_cpp_unordered_set_sum(&u), 26);
+        assert!(u.remove(6));
+        assert_eq!(HashSet::from(&*u), HashSet::from([5, 7, 8]));
+    }
+
+    #[test]
+    fn test_deque() {
+        let mut d = CxxDeque::<i64>::new();
+        assert_eq!(d.pop_front(), None);
+        d.push_back(2);
+        d.push_back(3);
+        d.push_front(1);
+        assert_eq!(d.len(), 3);
+        assert_eq!((d.front(), d.back()), (Some(&1), Some(&3)));
+        d[1] = 20;
+        assert_eq!(d.get(3), None);
+        assert_eq!(rxx_dummy_cpp_deque_sum(&d), 24);
+        assert_eq!(d.iter().rev().copied().collect::<Vec<_>>(), [3, 20, 1]);
+        assert_eq!(d.iter().len(), 3);
+        assert_eq!(d.pop_front(), Some(1));
+        assert_eq!(d.pop_back(), Some(3));
+        assert_eq!(VecDeque::from(&*d), [20]);
+        assert_eq!(format!("{:?}", d), "[20]");
+
+        let mut tasks = CxxDeque::<CxxVector<i64>>::new();
+        tasks.push_back(rxx_dummy_cpp_new_vector_i64(1));
+        tasks.push_front(rxx_dummy_cpp_new_vector_i64(2));
+        tasks[0].push(5);
+        assert_eq!(tasks.pop_front().unwrap().as_slice(), &[2, 5]);
+        assert_eq!(tasks.pop_back().unwrap().as_slice(), &[1]);
+        assert!(tasks.is_empty());
+    }
+
+    #[test]
+    fn test_list() {
+        let mut l = rxx_dummy_cpp_new_list(3);
+        assert_eq!(l.len(), 3);
+        assert_eq!((l.front(), l.back()), (Some(&0), Some(&2)));
+        *l.back_mut().unwrap() = 20;
+        l.push_front(-1);
+        assert_eq!(l.iter().copied().collect::<Vec<_>>(), [-1, 0, 1, 20]);
+        assert_eq!(l.iter().rev().copied().collect::<Vec<_>>(), [20, 1, 0, -1]);
+
+        let mut it = l.iter();
+        assert_eq!((it.next(), it.next_back()), (Some(&-1), Some(&20)));
+        assert_eq!(
+            (it.next(), it.next_back(), it.next()),
+            (Some(&0), Some(&1), None)
+        );
+
+        let mut other = [7, 8].into_iter().collect::<UniquePtr<CxxList<i64>>>();
+        l.splice(1, &mut other);
+        assert!(other.is_empty());
+        assert_eq!(
+            LinkedList::from(&*l),
+            LinkedList::from([-1, 7, 8, 0, 1, 20])
+        );
+        other.push_back(9);
+        l.append(&mut other);
+        assert_eq!(l.pop_back(), Some(9));
+        assert_eq!(l.pop_front(), Some(-1));
+        l.clear();
+        assert_eq!(l.pop_back(), None);
+        assert!(l.front().is_none());
+    }
+
+    #[test]
+    fn test_function() {
+        let add3 = rxx_dummy_cpp_make_adder(3);
+        assert!(!add3.is_empty());
+        assert_eq!(add3.call((4,)), 7);
+        let copy = add3.clone();
+        drop(add3);
+        assert_eq!(rxx_dummy_cpp_apply_twice(&copy, 1), 7);
+
+        let iota = rxx_dummy_cpp_make_iota(10);
+        let iota2 = iota.clone();
+        assert_eq!(iota.call((3,)).as_slice(), &[10, 11, 12]);
+        drop(iota);
+        assert_eq!(iota2.call((1,)).as_slice(), &[10]);
+
+        let mut total = 0;
+        let sink = rxx_dummy_cpp_make_sink(&mut total);
+        sink.call((5,));
+        sink.clone().call((6,));
+        drop(sink);
+        assert_eq!(total, 11);
+
+        let empty = CxxFunction::<AdderFn>::default();
+        assert!(empty.is_empty() && empty.clone().is_empty());
+        let r = std::panic::catch_unwind(|| empty.call((1,)));
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_function_from_rust() {
+        let mut base = 100;
+        let f = CxxFunction::<AdderFn>::from_rust(Box::new(move |x| {
+            base += 1;
+            base + x
+        }));
+        assert_eq!(f.call((1,)), 102);
+        assert_eq!(rxx_dummy_cpp_apply_twice(&f, 1), 206);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let seen = seen.clone();
+            CxxFunction::<SinkFn>::from(
+                Box::new(move |x: i64| seen.lock().unwrap().push(x)) as Box<dyn FnMut(i64) + Send>
+            )
+        };
+        rxx_dummy_cpp_call_copies(&sink, 3);
+        let copy = sink.clone();
+        drop(sink);
+        copy.call((7,));
+        assert_eq!(*seen.lock().unwrap(), [0, 1, 2, 7]);
+        assert_eq!(Arc::strong_count(&seen), 2);
+        drop(copy);
+        assert_eq!(Arc::strong_count(&seen), 1);
+
+        let iota = CxxFunction::<IotaFn>::from_rust(Box::new(|n| {
+            let mut v = rxx_dummy_cpp_new_vector_i64(0);
+            v.pop();
+            (0..n).for_each(|i| v.push(i));
+            v
+        }));
+        assert_eq!(iota.call((3,)).as_slice(), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn test_callback() {
+        let mut nodes = rxx_dummy_cpp_new_vector_i64(1);
+        nodes.push(2);
+        nodes.push(3);
+        let mut seen = Vec::new();
+        rxx_dummy_cpp_for_each_node(&nodes, CxxCallback::new(&mut |x| seen.push(x)));
+        assert_eq!(seen, [1, 2, 3]);
+
+        let mut words = Vec::new();
+        rxx_dummy_cpp_for_each_word(
+            3,
+            CxxCallback::new(&mut |w: &CxxString| words.push(w.to_str().to_owned())),
+        );
+        assert_eq!(words, ["w0", "w1", "w2"]);
+
+        let scale = 10;
+        let sum = rxx_dummy_cpp_fold(4, 0, (&mut |acc, i| acc + i * scale).into());
+        assert_eq!(sum, 60);
+
+        let v = rxx_dummy_cpp_collect(
+            3,
+            CxxCallback::new(&mut |i| {
+                let mut v = rxx_dummy_cpp_new_vector_i64(0);
+                v.pop();
+                (0..i).for_each(|_| v.push(i));
+                v
+            }),
+        );
+        assert_eq!(v.as_slice(), &[1, 2, 2]);
+
+        let mut calls = 0;
+        let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rxx_dummy_cpp_for_each_node(
+                &nodes,
+                CxxCallback::new(&mut |x| {
+                    calls += 1;
+                    assert!(x < 2, "node {x}");
+                }),
+            )
+        }));
+        assert_eq!(calls, 2);
+        let payload = r.unwrap_err();
+        assert_eq!(payload.downcast_ref::<String>().unwrap(), "node 2");
+
+        let r = std::panic::catch_unwind(|| {
+            rxx_dummy_cpp_fold(
+                4,
+                0,
+                (&mut |acc, i| if i == 1 { panic!("fold") } else { acc + i }).into(),
+            )
+        });
+        assert_eq!(*r.unwrap_err().downcast_ref::<&str>().unwrap(), "fold");
+
+        // nothing is left pending
+        assert_eq!(rxx_dummy_cpp_fold(4, 0, (&mut |acc, i| acc + i).into()), 6);
+    }
+
+    #[test]
+    fn test_callback_handle() {
+        let total = Arc::new(AtomicUsize::new(0));
+        let caller = std::thread::current().id();
+        let handle = {
+            let total = total.clone();
+            // unregistered below before it is dropped
+            unsafe {
+                CxxCallbackHandle::<fn(i64)>::new(move |ev: i64| {
+                    assert_ne!(std::thread::current().id(), caller);
+                    total.fetch_add(ev as usize, Ordering::SeqCst);
+                })
+            }
+        };
+        rxx_dummy_cpp_register_handler(&handle);
+        rxx_dummy_cpp_fire_event(3);
+        rxx_dummy_cpp_fire_event(4);
+        assert_eq!(total.load(Ordering::SeqCst), 7);
+
+        rxx_dummy_cpp_unregister_handlers();
+        drop(handle);
+        assert_eq!(Arc::strong_count(&total), 1);
+
+        // only called during the call
+        let double = unsafe { CxxCallbackHandle::<fn(i64) -> i64>::new(|x: i64| x * 2) };
+        assert_eq!(rxx_dummy_cpp_call_c_callback(21, &double), 42);
+    }
+
+    #[test]
+    fn test_path() {
+        let p = CxxPath::new(Path::new("/usr/lib"));
+        assert_eq!(p.as_path(), Path::new("/usr/lib"));
+        assert_eq!(rxx_dummy_cpp_parent_path(&p).as_path(), Path::new("/usr"));
+
+        let lib = p.join(Path::new("libc.so"));
+        assert_eq!(lib.to_path_buf(), PathBuf::from("/usr/lib/libc.so"));
+        assert_eq!(p.join(Path::new("/etc")).as_path(), Path::new("/etc"));
+
+        // not UTF-8, kept as raw bytes
+        let raw = Path::new(OsStr::from_bytes(b"data/\xff.bin"));
+        assert_eq!(CxxPath::new(raw).as_path(), raw);
+
+        let mut parts = rxx_dummy_cpp_path_parts(&lib);
+        let parts_str: Vec<_> = parts
+            .iter()
+            .map(|p| p.as_path().to_str().unwrap())
+            .collect();
+        assert_eq!(parts_str, ["/", "usr", "lib", "libc.so"]);
+        parts.push_path(raw);
+        assert_eq!(parts.len(), 5);
+        assert_eq!(parts[4].as_path(), raw);
+        assert_eq!(parts.get_pin_mut(1).unwrap().as_path(), Path::new("usr"));
+        assert!(parts.get_pin_mut(5).is_none());
+    }
+
+    #[test]
+    fn test_complex() {
+        let a = CxxComplex::new(1.0, 2.0);
+        let b = CxxComplex::from((3.0, -1.0));
+        assert_eq!(rxx_dummy_cpp_complex_mul(a, b), a * b);
+        assert_eq!(<(f64, f64)>::from(a * b), (5.0, 5.0));
+        assert_eq!((a * b) / b, a);
+        assert_eq!(a + b - b, a);
+        assert_eq!(-a, CxxComplex::new(-1.0, -2.0));
+        assert_eq!(a.norm(), 5.0);
+
+        let mut c = a;
+        c *= a.conj();
+        assert_eq!(c, CxxComplex::new(5.0, 0.0));
+
+        let f = CxxComplex::new(1.5f32, 0.5);
+        assert_eq!(rxx_dummy_cpp_complex_conj_f32(f), f.conj());
+
+        let mut s = rxx_dummy_cpp_spectrum(3);
+        assert_eq!(s[2], CxxComplex::new(2.0, -2.0));
+        assert_eq!(rxx_dummy_cpp_spectrum_energy(&s), 10.0);
+        for c in s.iter_mut() {
+            *c += CxxComplex::new(0.0, 1.0);
+        }
+        s.push(CxxComplex::new(3.0, 0.0));
+        let energy: f64 = s.iter().map(|c| c.norm()).sum();
+        assert_eq!(rxx_dummy_cpp_spectrum_energy(&s), energy);
+    }
+
+    #[test]
+    fn test_error_code() {
+        assert_eq!(rxx_dummy_cpp_checked_div(7, 2), Ok(3));
+        let e = rxx_dummy_cpp_checked_div(7, 0).unwrap_err();
+        assert_eq!(e.category_name(), "generic");
+        assert_eq!(e.raw_os_error(), Some(22)); // EINVAL
+        assert_eq!(io::Error::from(e).kind(), io::ErrorKind::InvalidInput);
+
+        assert_eq!(rxx_dummy_cpp_remove_missing(0), Ok(()));
+        let e = rxx_dummy_cpp_remove_missing(1).unwrap_err();
+        assert_eq!(e.category_name(), "system");
+        assert_eq!(io::Error::from(e).kind(), io::ErrorKind::NotFound);
+
+        assert_eq!(
+            rxx_dummy_cpp_checked_range(3).map(|v| v.to_vec()),
+            Ok(vec![0, 1, 2])
+        );
+        let e = rxx_dummy_cpp_checked_range(-5).err().unwrap();
+        assert_eq!(e.value(), 5);
+        assert_eq!(e.category_name(), "dummy");
+        assert_eq!(e.raw_os_error(), None);
+        assert_eq!(e.message(), "dummy error 5");
+        let e = io::Error::from(e);
+        assert_eq!(e.kind(), io::ErrorKind::Other);
+        assert_eq!(e.to_string(), "dummy error 5");
+
+        assert_eq!(rxx_dummy_cpp_parse_port(80), Ok(80));
+        let e = rxx_dummy_cpp_parse_port(70000).unwrap_err();
+        assert_eq!(e.raw_os_error(), Some(34)); // ERANGE
+
+        assert!(!CxxErrorCode::default().is_err());
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut ss = CxxStringStream::from_bytes(b"1 2 3\n40");
+        assert_eq!(rxx_dummy_cpp_sum_words(ss.as_istream()), 46);
+        assert!(ss.as_istream().eof());
+        ss.as_istream().clear();
+        assert!(ss.as_istream().good());
+
+        let mut out = CxxStringStream::new();
+        rxx_dummy_cpp_log_to(out.as_ostream(), 2);
+        write!(out.as_ostream(), "end {}", 3).unwrap();
+        out.as_ostream().flush().unwrap();
+        assert_eq!(out.to_bytes(), b"line 0\nline 1\nend 3");
+
+        let mut s = String::new();
+        out.as_istream().read_to_string(&mut s).unwrap();
+        assert_eq!(s, "line 0\nline 1\nend 3");
+        assert!(out.as_istream().eof());
+
+        let mut ss = CxxStringStream::from_bytes(b"ab\ncd\n");
+        let input = ss.as_istream();
+        let mut head = [0; 1];
+        input.read_exact(&mut head).unwrap();
+        assert_eq!(&head, b"a");
+        let lines: Vec<_> = input.lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["b", "cd"]);
+
+        let mut ss = CxxStringStream::from_bytes(b"xyz");
+        let input = ss.as_istream();
+        assert_eq!(input.fill_buf().unwrap(), b"xyz");
+        // clamped to the get area
+        input.consume(usize::MAX);
+        assert_eq!(input.fill_buf().unwrap(), b"");
+
+        let null = rxx_dummy_cpp_null_ostream();
+        let e = null.write_all(b"lost").unwrap_err();
+        assert_eq!(e.to_string(), "C++ stream error, badbit is set");
+        assert!(null.bad() && null.fail());
+    }
+
+    #[test]
+    fn test_chrono() {
+        let timeout = CxxMilliseconds::try_from(Duration::from_micros(1500)).unwrap();
+        assert_eq!(timeout.count(), 1);
+        let timeout = rxx_dummy_cpp_double_timeout(timeout);
+        assert_eq!(Duration::try_from(timeout), Ok(Duration::from_millis(2)));
+
+        let negative = CxxMilliseconds::from_count(-1);
+        assert_eq!(Duration::try_from(negative), Err(ChronoError::Negative));
+        assert_eq!(
+            CxxNanoseconds::try_from(Duration::from_secs(u64::MAX)),
+            Err(ChronoError::Overflow)
+        );
+        assert_eq!(
+            Duration::try_from(CxxHours::from_count(2)),
+            Ok(Duration::from_secs(7200))
+        );
+
+        // sub-nanosecond period like `std::pico`
+        type Picoseconds = CxxDuration<1, 1_000_000_000_000>;
+        assert_eq!(
+            Picoseconds::try_from(Duration::from_nanos(3)).map(|d| d.count()),
+            Ok(3000)
+        );
+        assert_eq!(
+            Duration::try_from(Picoseconds::from_count(1500)),
+            Ok(Duration::from_nanos(1))
+        );
+        assert_eq!(
+            Picoseconds::try_from(Duration::MAX),
+            Err(ChronoError::Overflow)
+        );
+        assert_eq!(
+            Duration::try_from(CxxDuration::<{ i64::MAX }, 1>::from_count(i64::MAX)),
+            Err(ChronoError::Overflow)
+        );
+
+        let now = SystemTime::now();
+        let later = rxx_dummy_cpp_add_hour(now.try_into().unwrap());
+        let later = SystemTime::try_from(later).unwrap();
+        assert_eq!(
+            later.duration_since(now).unwrap(),
+            Duration::from_secs(3600)
+        );
+
+        let before_epoch = UNIX_EPOCH - Duration::from_secs(10);
+        let t = CxxSystemTime::try_from(before_epoch).unwrap();
+        assert_eq!(t.since_epoch().count(), -10_000_000_000);
+        assert_eq!(SystemTime::try_from(t), Ok(before_epoch));
+
+        let start = CxxSteadyInstant::now();
+        let end = start.checked_add(Duration::from_millis(250)).unwrap();
+        assert_eq!(rxx_dummy_cpp_steady_millis(start, end), 250);
+        assert_eq!(
+            end.checked_duration_since(start),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(start.checked_duration_since(end), None);
+        assert!(CxxSteadyInstant::now() >= start);
+    }
+
+    #[test]
+    fn test_array() {
+        let z = rxx_dummy_cpp_cross([1.0, 0.0, 0.0].into(), [0.0, 1.0, 0.0].into());
+        assert_eq!(z.into_inner(), [0.0, 0.0, 1.0]);
+
+        let mut points = rxx_dummy_cpp_new_points(2);
+        assert_eq!(points.as_slice(), &[[0.0; 3].into(), [1.0; 3].into()]);
+        points.push(CxxArray::new([2.0, 0.0, 0.0]));
+        assert_eq!(points[2][0], 2.0);
+
+        let mut p = DummyPoint {
+            id: 1,
+            pos: CxxArray::default(),
+        };
+        p.pos[2] = 3.0;
+        assert_eq!(p.id, 1);
+        assert_eq!(rxx_dummy_cpp_point_norm2(&p), 9.0);
+    }
+
+    #[test]
+    fn test_pair_tuple() {
+        let p = rxx_dummy_cpp_new_pair(3);
+        assert_eq!(p, CxxPair::new(3, 1.5));
+        assert_eq!(<(i32, f64)>::from(p), (3, 1.5));
+
+        let p = rxx_dummy_cpp_new_pair_vector(5);
+        assert_eq!(p.first, 5);
+        assert_eq!(p.second.as_slice(), &[5, 6]);
+
+        let mut t = rxx_dummy_cpp_new_tuple(4);
+        let (a, b, v) = t.as_refs();
+        assert_eq!((*a, *b, v.as_slice()), (4, 2.0, &[4, 5][..]));
+        assert_eq!(rxx_dummy_cpp_tuple_sum(&t), 15.0);
+
+        let (a, _, v) = t.as_mut_refs();
+        *a = 10;
+        v.push(1);
+        assert_eq!(rxx_dummy_cpp_tuple_sum(&t), 22.0);
+
+        let (a, b, v) = t.into_tuple();
+        assert_eq!((a, b, v.as_slice()), (10, 2.0, &[4, 5, 1][..]));
+
+        let t = CxxTuple::from((1, 0.5, v));
+        assert_eq!(rxx_dummy_cpp_tuple_sum(&t), 11.5);
+    }
+
+    #[test]
+    fn test_variant() {
+        let m = rxx_dummy_cpp_new_msg(0);
+        assert_eq!(m.index(), Some(0));
+        assert!(matches!(m.as_enum(), DummyMsgRef::Int(&1)));
+
+        let m = rxx_dummy_cpp_new_msg(2);
+        match m.as_enum() {
+            DummyMsgRef::Values(v) => assert_eq!(v.as_slice(), &[1, 2, 3]),
+            _ => panic!("expected Values"),
+        }
+        assert_eq!(rxx_dummy_cpp_msg_sum(&m), 6.0);
+
+        let mut m = CxxVariant::from(DummyMsg::Float(2.5));
+        assert!(matches!(m.as_enum(), DummyMsgRef::Float(&v) if v == 2.5));
+        assert_eq!(rxx_dummy_cpp_msg_sum(&m), 2.5);
+
+        m.emplace(DummyMsg::Values(rxx_dummy_cpp_new_vector_i64(4)));
+        assert_eq!(m.index(), Some(2));
+        assert_eq!(rxx_dummy_cpp_msg_sum(&m), 4.0);
+
+        m.emplace(DummyMsg::Int(3));
+        assert!(matches!(m.as_enum(), DummyMsgRef::Int(&3)));
     }
 
     #[test]
@@ -0,0 +1,116 @@
+use crate::shared_ptr::{SharedPtr, SharedPtrTarget};
+use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
+use core::ffi::c_void;
+use core::mem::MaybeUninit;
+
+// implemented on the derived type, all pointer adjustments are done by C++
+pub trait DerivedFrom<B> {
+    unsafe fn __shared_downcast(this: *const c_void, out: *mut c_void);
+    unsafe fn __shared_static_downcast(this: *const c_void, out: *mut c_void);
+    unsafe fn __shared_upcast(this: *const c_void, out: *mut c_void);
+    unsafe fn __unique_downcast(this: *mut c_void, out: *mut c_void);
+    unsafe fn __unique_static_downcast(this: *mut c_void, out: *mut c_void);
+    unsafe fn __unique_upcast(this: *mut c_void, out: *mut c_void);
+}
+
+impl<T: SharedPtrTarget> SharedPtr<T> {
+    /// `std::dynamic_pointer_cast<D>`, returns `None` if the pointee is not a `D`.
+    pub fn downcast<D>(&self) -> Option<SharedPtr<D>>
+    where
+        D: SharedPtrTarget + DerivedFrom<T>,
+    {
+        let mut out = MaybeUninit::<SharedPtr<D>>::uninit();
+        let out = unsafe {
+            D::__shared_downcast(
+                self as *const Self as *const c_void,
+                out.as_mut_ptr().cast(),
+            );
+            out.assume_init()
+        };
+
+        if out.is_null() {
+            None
+        } else {
+            Some(out)
+        }
+    }
+
+    /// `std::static_pointer_cast<D>`, the pointee must be a `D`.
+    pub unsafe fn downcast_unchecked<D>(&self) -> SharedPtr<D>
+    where
+        D: SharedPtrTarget + DerivedFrom<T>,
+    {
+        let mut out = MaybeUninit::<SharedPtr<D>>::uninit();
+        D::__shared_static_downcast(
+            self as *const Self as *const c_void,
+            out.as_mut_ptr().cast(),
+        );
+        out.assume_init()
+    }
+
+    pub fn upcast<B>(&self) -> SharedPtr<B>
+    where
+        B: SharedPtrTarget,
+        T: DerivedFrom<B>,
+    {
+        let mut out = MaybeUninit::<SharedPtr<B>>::uninit();
+        unsafe {
+            T::__shared_upcast(
+                self as *const Self as *const c_void,
+                out.as_mut_ptr().cast(),
+            );
+            out.assume_init()
+        }
+    }
+}
+
+impl<T: UniquePtrTarget> UniquePtr<T> {
+    /// Moves the pointee into a `UniquePtr<D>` if it is a `D`, gives `self`
+    /// back untouched otherwise.
+    pub fn downcast<D>(mut self) -> Result<UniquePtr<D>, Self>
+    where
+        D: UniquePtrTarget + DerivedFrom<T>,
+    {
+        let mut out = MaybeUninit::<UniquePtr<D>>::uninit();
+        let out = unsafe {
+            D::__unique_downcast(
+                &mut self as *mut Self as *mut c_void,
+                out.as_mut_ptr().cast(),
+            );
+            out.assume_init()
+        };
+
+        if out.is_null() {
+            Err(self)
+        } else {
+            Ok(out)
+        }
+    }
+
+    pub unsafe fn downcast_unchecked<D>(mut self) -> UniquePtr<D>
+    where
+        D: UniquePtrTarget + DerivedFrom<T>,
+    {
+        let mut out = MaybeUninit::<UniquePtr<D>>::uninit();
+        D::__unique_static_downcast(
+            &mut self as *mut Self as *mut c_void,
+            out.as_mut_ptr().cast(),
+        );
+        out.assume_init()
+    }
+
+    pub fn upcast<B>(mut self) -> UniquePtr<B>
+    where
+        B: UniquePtrTarget,
+        T: DerivedFrom<B>,
+    {
+        let mut out = MaybeUninit::<UniquePtr<B>>::uninit();
+        unsafe {
+            T::__unique_upcast(
+                &mut self as *mut Self as *mut c_void,
+                out.as_mut_ptr().cast(),
+            );
+            out.assume_init()
+        }
+    }
+}
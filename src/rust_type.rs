@@ -0,0 +1,64 @@
+use crate::shared_ptr::{SharedPtr, SharedPtrTarget};
+use crate::unique_ptr::{UniquePtr, UniquePtrTarget};
+use core::ffi::c_void;
+use core::mem::MaybeUninit;
+use std::sync::Arc;
+
+/// Rust type exported with `genrs_rust_type!`, C++ names it with
+/// `RXX_RUST_TYPE` from `wrapper.hh`.
+pub unsafe trait RustType: UniquePtrTarget + SharedPtrTarget + Sized {}
+
+extern "C" {
+    fn rxx_shared_ptr_from_raw(
+        ptr: *mut c_void,
+        deleter: unsafe extern "C" fn(*mut c_void),
+        out: *mut c_void,
+    );
+}
+
+unsafe extern "C" fn drop_box<T>(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr as *mut T));
+}
+
+unsafe extern "C" fn drop_arc<T>(ptr: *mut c_void) {
+    drop(Arc::from_raw(ptr as *const T));
+}
+
+unsafe fn shared_from_raw<T: SharedPtrTarget>(
+    ptr: *mut c_void,
+    deleter: unsafe extern "C" fn(*mut c_void),
+) -> SharedPtr<T> {
+    let mut out = MaybeUninit::<SharedPtr<T>>::uninit();
+    rxx_shared_ptr_from_raw(ptr, deleter, out.as_mut_ptr().cast());
+    out.assume_init()
+}
+
+impl<T: RustType> SharedPtr<T> {
+    /// Moves `value` to the heap, the last `std::shared_ptr` copy drops it.
+    pub fn from_rust(value: T) -> Self
+    where
+        T: Send + Sync,
+    {
+        let ptr = Box::into_raw(Box::new(value));
+        unsafe { shared_from_raw(ptr.cast(), drop_box::<T>) }
+    }
+
+    /// Keeps one strong count of `value` until the last `std::shared_ptr` copy
+    /// is destroyed.
+    pub fn from_arc(value: Arc<T>) -> Self
+    where
+        T: Send + Sync,
+    {
+        let ptr = Arc::into_raw(value) as *mut T;
+        unsafe { shared_from_raw(ptr.cast(), drop_arc::<T>) }
+    }
+}
+
+impl<T: RustType> UniquePtr<T> {
+    pub fn from_box(value: Box<T>) -> Self
+    where
+        T: Send,
+    {
+        unsafe { UniquePtr::from_raw(Box::into_raw(value).cast()) }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::shared_ptr::{SharedPtr, SharedPtrTarget};
+use crate::weak_ptr::{WeakPtr, WeakPtrTarget};
+use core::ffi::c_void;
+use core::fmt::{self, Display};
+use core::mem::MaybeUninit;
+
+/// The object is not owned by any `std::shared_ptr`, C++ throws
+/// `std::bad_weak_ptr` in this case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadWeakPtr;
+
+impl Display for BadWeakPtr {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("bad_weak_ptr")
+    }
+}
+
+impl std::error::Error for BadWeakPtr {}
+
+/// Binding to types deriving from C++ `std::enable_shared_from_this<T>`.
+pub trait SharedFromThis: SharedPtrTarget + Sized {
+    unsafe fn __shared_from_this(this: *const c_void, out: *mut c_void) -> bool;
+
+    fn shared_from_this(&self) -> Result<SharedPtr<Self>, BadWeakPtr> {
+        let mut out = MaybeUninit::<SharedPtr<Self>>::uninit();
+        unsafe {
+            let ok = Self::__shared_from_this(
+                self as *const Self as *const c_void,
+                out.as_mut_ptr().cast(),
+            );
+            let out = out.assume_init();
+            if ok {
+                Ok(out)
+            } else {
+                Err(BadWeakPtr)
+            }
+        }
+    }
+
+    fn weak_from_this(&self) -> WeakPtr<Self>
+    where
+        Self: WeakPtrTarget,
+    {
+        match self.shared_from_this() {
+            Ok(shared) => shared.downgrade(),
+            Err(_) => WeakPtr::null(),
+        }
+    }
+}
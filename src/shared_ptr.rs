@@ -2,7 +2,8 @@ use crate::weak_ptr::{WeakPtr, WeakPtrTarget};
 use core::ffi::c_void;
 use core::fmt::{self, Debug, Display};
 use core::marker::PhantomData;
-use core::ops::{Deref, DerefMut};
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
 use core::pin::Pin;
 use std::mem::MaybeUninit;
 
@@ -11,6 +12,18 @@ pub trait SharedPtrTarget {
     unsafe fn __clone(this: *const c_void, out: *mut c_void);
 }
 
+/// Types constructible with `std::make_shared` from Rust, opt in with
+/// `genrs_make_shared!` and `genc_make_shared`.
+///
+/// # Safety
+///
+/// The Rust type must have the layout of the C++ type, `SharedPtr::new`
+/// hands a Rust value to C++ to be moved from.
+pub unsafe trait MakeShared: SharedPtrTarget {
+    unsafe fn __new(value: *mut c_void, out: *mut c_void);
+    unsafe fn __new_default(out: *mut c_void);
+}
+
 #[repr(C)]
 pub struct SharedPtr<T: SharedPtrTarget> {
     ptr: *mut c_void,
@@ -39,30 +52,73 @@ impl<T: SharedPtrTarget> SharedPtr<T> {
         unsafe { (self.ptr as *const T).as_ref() }
     }
 
-    pub fn as_mut(&mut self) -> Option<&mut T>
-    where
-        T: Unpin,
-    {
-        unsafe { (self.ptr as *mut T).as_mut() }
+    pub fn use_count(&self) -> usize {
+        extern "C" {
+            fn rxx_shared_ptr_use_count(this: *const c_void) -> usize;
+        }
+        unsafe { rxx_shared_ptr_use_count(self as *const Self as *const c_void) }
     }
 
-    pub fn as_pin_mut(&mut self) -> Option<Pin<&mut T>> {
-        unsafe {
-            let p = (self.ptr as *mut T).as_mut()?;
-            Some(Pin::new_unchecked(p))
+    /// Mutable access if no other `SharedPtr` shares ownership of the pointee.
+    ///
+    /// # Safety
+    ///
+    /// Only `std::shared_ptr` owners are counted. While the returned
+    /// reference is alive no `WeakPtr` to the pointee may be upgraded, on any
+    /// thread, and the pointee must not be reachable through other Rust
+    /// handles, such as `Arc` clones of the one passed to `from_arc`.
+    pub unsafe fn get_mut(&mut self) -> Option<Pin<&mut T>> {
+        if self.is_null() || self.use_count() != 1 {
+            return None;
         }
+        Some(self.get_mut_unchecked())
     }
 
-    pub fn pin_mut(&mut self) -> Pin<&mut T> {
-        match self.as_pin_mut() {
-            Some(target) => target,
+    /// Mutable access regardless of other owners, no other reference to the
+    /// pointee may be used while the returned one is alive.
+    pub unsafe fn get_mut_unchecked(&mut self) -> Pin<&mut T> {
+        match (self.ptr as *mut T).as_mut() {
+            Some(target) => Pin::new_unchecked(target),
             None => panic!(
-                "called pin_mut on a null UniquePtr<{}>",
+                "called get_mut_unchecked on a null SharedPtr<{}>",
                 std::any::type_name::<T>(),
             ),
         }
     }
 
+    /// Returns a `SharedPtr` to a sub-object of the pointee which shares
+    /// ownership with `self`, like the C++ aliasing constructor.
+    pub fn project<U, F>(&self, f: F) -> SharedPtr<U>
+    where
+        U: SharedPtrTarget,
+        F: FnOnce(&T) -> &U,
+    {
+        let target = match self.as_ref() {
+            Some(target) => f(target),
+            None => panic!(
+                "called project on a null SharedPtr<{}>",
+                std::any::type_name::<T>(),
+            ),
+        };
+        unsafe { self.alias(target) }
+    }
+
+    /// Shares ownership with `self` but points to `ptr`, which must stay valid
+    /// as long as the pointee of `self`.
+    pub(crate) unsafe fn alias<U: SharedPtrTarget>(&self, ptr: *const U) -> SharedPtr<U> {
+        extern "C" {
+            fn rxx_shared_ptr_alias(this: *const c_void, ptr: *mut c_void, out: *mut c_void);
+        }
+
+        let mut out = MaybeUninit::<SharedPtr<U>>::uninit();
+        rxx_shared_ptr_alias(
+            self as *const Self as *const c_void,
+            ptr as *mut c_void,
+            out.as_mut_ptr().cast(),
+        );
+        out.assume_init()
+    }
+
     pub fn downgrade(&self) -> WeakPtr<T>
     where
         T: WeakPtrTarget,
@@ -78,8 +134,34 @@ impl<T: SharedPtrTarget> SharedPtr<T> {
     }
 }
 
-unsafe impl<T> Send for SharedPtr<T> where T: Send + SharedPtrTarget {}
-unsafe impl<T> Sync for SharedPtr<T> where T: Sync + SharedPtrTarget {}
+impl<T: MakeShared> SharedPtr<T> {
+    /// `std::make_shared<T>(std::move(value))`.
+    pub fn new(value: T) -> Self {
+        // C++ calls move constructor followed by destructor on `value`
+        let mut value = ManuallyDrop::new(value);
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            T::__new(
+                &mut *value as *mut T as *mut c_void,
+                out.as_mut_ptr().cast(),
+            );
+            out.assume_init()
+        }
+    }
+
+    /// `std::make_shared<T>()`.
+    pub fn new_default() -> Self {
+        let mut out = MaybeUninit::<Self>::uninit();
+        unsafe {
+            T::__new_default(out.as_mut_ptr().cast());
+            out.assume_init()
+        }
+    }
+}
+
+// pointees are shared across clones like `Arc`
+unsafe impl<T> Send for SharedPtr<T> where T: Send + Sync + SharedPtrTarget {}
+unsafe impl<T> Sync for SharedPtr<T> where T: Send + Sync + SharedPtrTarget {}
 
 impl<T: SharedPtrTarget> Clone for SharedPtr<T> {
     fn clone(&self) -> Self {
@@ -116,18 +198,6 @@ impl<T: SharedPtrTarget> Deref for SharedPtr<T> {
     }
 }
 
-impl<T: SharedPtrTarget + Unpin> DerefMut for SharedPtr<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        match self.as_mut() {
-            Some(target) => target,
-            None => panic!(
-                "called deref_mut on a null SharedPtr<{}>",
-                std::any::type_name::<T>(),
-            ),
-        }
-    }
-}
-
 impl<T: Debug + SharedPtrTarget> Debug for SharedPtr<T> {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self.as_ref() {